@@ -29,7 +29,7 @@ impl<T> SpinLock<T> {
         }
     }
 
-    fn lock(&self) -> SpinLockGuard<T> {
+    fn lock(&self) -> SpinLockGuard<'_, T> {
         loop {
             while self.lock.load(Ordering::Relaxed) {
                 hint::spin_loop();
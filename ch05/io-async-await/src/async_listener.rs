@@ -1,13 +1,14 @@
 use std::{
     future::Future,
-    io::BufWriter,
+    io,
     net::{SocketAddr, TcpListener, TcpStream},
     os::fd::AsRawFd,
+    pin::Pin,
     sync::Arc,
-    task::Poll,
+    task::{Context, Poll},
 };
 
-use crate::{async_reader::AsyncReader, io_selector::IOSelector};
+use crate::{async_tcp_stream::AsyncTcpStream, io_selector::IOSelector};
 
 pub struct AsyncListener {
     listener: TcpListener,
@@ -15,14 +16,18 @@ pub struct AsyncListener {
 }
 
 impl AsyncListener {
-    pub fn listen(addr: &str, selector: Arc<IOSelector>) -> AsyncListener {
-        let listener = TcpListener::bind(addr).unwrap();
-        listener.set_nonblocking(true).unwrap();
+    pub fn listen(addr: &str, selector: Arc<IOSelector>) -> io::Result<AsyncListener> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
 
-        AsyncListener { listener, selector }
+        Ok(AsyncListener { listener, selector })
     }
 
-    pub fn accept(&self) -> Accept {
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    pub fn accept(&self) -> Accept<'_> {
         Accept { listener: self }
     }
 }
@@ -38,31 +43,18 @@ pub struct Accept<'a> {
 }
 
 impl Future for Accept<'_> {
-    type Output = (AsyncReader, BufWriter<TcpStream>, SocketAddr);
-
-    fn poll(
-        self: std::pin::Pin<&mut Self>,
-        cx: &mut std::task::Context<'_>,
-    ) -> std::task::Poll<Self::Output> {
-        match self.listener.listener.accept() {
-            Ok((stream, addr)) => {
-                let stream0 = stream.try_clone().unwrap();
-                Poll::Ready((
-                    AsyncReader::new(stream0, self.listener.selector.clone()),
-                    BufWriter::new(stream),
-                    addr,
-                ))
-            }
-            // 받아들일 커넥션이 없는 경우에는 epoll에 등록
-            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
-                self.listener.selector.register(
-                    libc::EPOLLIN,
-                    self.listener.listener.as_raw_fd(),
-                    cx.waker().clone(),
-                );
-                Poll::Pending
-            }
-            Err(e) => panic!("accept error: {}", e),
+    type Output = io::Result<(AsyncTcpStream, SocketAddr)>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let listener = self.listener;
+        let fd = listener.listener.as_raw_fd();
+        match listener.selector.poll_accept(fd, cx) {
+            Poll::Ready(Ok((stream, addr))) => Poll::Ready(Ok((
+                AsyncTcpStream::new(TcpStream::from(stream), listener.selector.clone())?,
+                addr,
+            ))),
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Pending => Poll::Pending,
         }
     }
 }
@@ -1,8 +1,8 @@
 use std::{
     future::Future,
-    io::{BufRead, BufReader},
+    io,
     net::TcpStream,
-    os::fd::{AsRawFd, RawFd},
+    os::fd::AsRawFd,
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
@@ -10,30 +10,40 @@ use std::{
 
 use crate::io_selector::IOSelector;
 
+const BUF_SIZE: usize = 8 * 1024;
+
 pub struct AsyncReader {
-    fd: RawFd,
-    reader: BufReader<TcpStream>,
+    stream: TcpStream,
+    // 수신한 데이터 중 아직 줄로 꺼내지 않은 부분은 buf[pos..filled]
+    buf: Box<[u8]>,
+    pos: usize,
+    filled: usize,
+    // 줄바꿈을 받기 전까지 읽은 줄의 일부
+    line: Vec<u8>,
     selector: Arc<IOSelector>,
 }
 
 impl AsyncReader {
-    pub fn new(stream: TcpStream, selector: Arc<IOSelector>) -> AsyncReader {
-        stream.set_nonblocking(true).unwrap();
-        AsyncReader {
-            fd: stream.as_raw_fd(),
-            reader: BufReader::new(stream),
+    pub fn new(stream: TcpStream, selector: Arc<IOSelector>) -> io::Result<AsyncReader> {
+        stream.set_nonblocking(true)?;
+        Ok(AsyncReader {
+            stream,
+            buf: vec![0; BUF_SIZE].into_boxed_slice(),
+            pos: 0,
+            filled: 0,
+            line: Vec::new(),
             selector,
-        }
+        })
     }
 
-    pub fn read_line(&mut self) -> ReadLine {
+    pub fn read_line(&mut self) -> ReadLine<'_> {
         ReadLine { reader: self }
     }
 }
 
 impl Drop for AsyncReader {
     fn drop(&mut self) {
-        self.selector.unregister(self.fd);
+        self.selector.unregister(self.stream.as_raw_fd());
     }
 }
 
@@ -42,20 +52,51 @@ pub struct ReadLine<'a> {
 }
 
 impl Future for ReadLine<'_> {
-    type Output = Option<String>;
+    // EOF라면 None
+    type Output = io::Result<Option<String>>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let mut line = String::new();
-        match self.reader.reader.read_line(&mut line) {
-            Ok(0) => Poll::Ready(None),
-            Ok(_) => Poll::Ready(Some(line)),
-            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
-                self.reader
-                    .selector
-                    .register(libc::EPOLLIN, self.reader.fd, cx.waker().clone());
-                Poll::Pending
+        let reader = &mut *self.reader;
+        loop {
+            if reader.pos == reader.filled {
+                let n =
+                    match reader
+                        .selector
+                        .poll_recv(reader.stream.as_raw_fd(), cx, &mut reader.buf)
+                    {
+                        Poll::Ready(Ok(n)) => n,
+                        Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                        Poll::Pending => return Poll::Pending,
+                    };
+                if n == 0 {
+                    // EOF 전에 받은 줄바꿈 없는 데이터도 한 줄로 반환한다.
+                    if reader.line.is_empty() {
+                        return Poll::Ready(Ok(None));
+                    }
+                    break;
+                }
+                reader.pos = 0;
+                reader.filled = n;
+            }
+
+            let available = &reader.buf[reader.pos..reader.filled];
+            match available.iter().position(|&b| b == b'\n') {
+                Some(i) => {
+                    reader.line.extend_from_slice(&available[..=i]);
+                    reader.pos += i + 1;
+                    break;
+                }
+                None => {
+                    reader.line.extend_from_slice(available);
+                    reader.pos = reader.filled;
+                }
             }
-            Err(_) => Poll::Ready(None),
+        }
+
+        let line = std::mem::take(&mut reader.line);
+        match String::from_utf8(line) {
+            Ok(line) => Poll::Ready(Ok(Some(line))),
+            Err(err) => Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidData, err))),
         }
     }
 }
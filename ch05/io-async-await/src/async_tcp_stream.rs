@@ -0,0 +1,174 @@
+use std::{
+    future::Future,
+    io,
+    net::{self, SocketAddr, TcpStream},
+    os::fd::{AsRawFd, RawFd},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use crate::{
+    async_io::{AsyncRead, AsyncWrite},
+    io_selector::IOSelector,
+    socket::{inet_sockaddr, poll_connect, start_connect},
+};
+
+pub struct AsyncTcpStream {
+    stream: TcpStream,
+    selector: Arc<IOSelector>,
+}
+
+impl AsyncTcpStream {
+    pub fn new(stream: TcpStream, selector: Arc<IOSelector>) -> io::Result<AsyncTcpStream> {
+        stream.set_nonblocking(true)?;
+        Ok(AsyncTcpStream { stream, selector })
+    }
+
+    pub fn connect(addr: SocketAddr, selector: Arc<IOSelector>) -> Connect {
+        Connect {
+            addr,
+            stream: None,
+            selector,
+        }
+    }
+
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.stream.peer_addr()
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.stream.local_addr()
+    }
+
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.stream.set_nodelay(nodelay)
+    }
+}
+
+impl AsRawFd for AsyncTcpStream {
+    fn as_raw_fd(&self) -> RawFd {
+        self.stream.as_raw_fd()
+    }
+}
+
+impl Drop for AsyncTcpStream {
+    fn drop(&mut self) {
+        self.selector.unregister(self.stream.as_raw_fd());
+    }
+}
+
+// 읽기와 쓰기를 서로 다른 태스크에서 동시에 할 수 있도록 &AsyncTcpStream에도 구현한다.
+impl AsyncRead for &AsyncTcpStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.selector.poll_recv(self.stream.as_raw_fd(), cx, buf)
+    }
+}
+
+impl AsyncWrite for &AsyncTcpStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.selector.poll_send(self.stream.as_raw_fd(), cx, buf)
+    }
+
+    // io_uring에서는 제출한 송신이 완료될 때까지 기다린다.
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.selector.poll_flush(self.stream.as_raw_fd(), cx)
+    }
+
+    // 아직 보내지 않은 데이터를 보낸 후에 닫는다.
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.selector.poll_flush(self.stream.as_raw_fd(), cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(self.stream.shutdown(net::Shutdown::Write)),
+            result => result,
+        }
+    }
+}
+
+impl AsyncRead for AsyncTcpStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut &*self).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for AsyncTcpStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut &*self).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut &*self).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut &*self).poll_shutdown(cx)
+    }
+}
+
+// 논블로킹 connect
+// 연결이 완료되면 EPOLLOUT으로 통지된다.
+pub struct Connect {
+    addr: SocketAddr,
+    stream: Option<TcpStream>,
+    selector: Arc<IOSelector>,
+}
+
+impl Future for Connect {
+    type Output = io::Result<AsyncTcpStream>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let stream = match self.stream.take() {
+            Some(stream) => stream,
+            None => {
+                let domain = match self.addr {
+                    SocketAddr::V4(_) => libc::AF_INET,
+                    SocketAddr::V6(_) => libc::AF_INET6,
+                };
+                let (storage, len) = inet_sockaddr(&self.addr);
+                match start_connect(domain, &storage, len) {
+                    Ok((fd, true)) => {
+                        let stream = TcpStream::from(fd);
+                        return Poll::Ready(AsyncTcpStream::new(stream, self.selector.clone()));
+                    }
+                    Ok((fd, false)) => TcpStream::from(fd),
+                    Err(err) => return Poll::Ready(Err(err)),
+                }
+            }
+        };
+
+        match poll_connect(&self.selector, stream.as_raw_fd(), cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(AsyncTcpStream::new(stream, self.selector.clone())),
+            Poll::Ready(Err(err)) => {
+                self.selector.unregister(stream.as_raw_fd());
+                Poll::Ready(Err(err))
+            }
+            Poll::Pending => {
+                self.stream = Some(stream);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for Connect {
+    fn drop(&mut self) {
+        if let Some(stream) = &self.stream {
+            self.selector.unregister(stream.as_raw_fd());
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    future::Future,
+    io,
+    net::{SocketAddr, UdpSocket},
+    os::fd::{AsRawFd, RawFd},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use crate::{async_io::poll_io, io_selector::IOSelector};
+
+// 송신과 수신을 서로 다른 태스크에서 동시에 할 수 있도록 &self로 받는다.
+pub struct AsyncUdpSocket {
+    socket: UdpSocket,
+    selector: Arc<IOSelector>,
+}
+
+impl AsyncUdpSocket {
+    pub fn bind(addr: &str, selector: Arc<IOSelector>) -> io::Result<AsyncUdpSocket> {
+        let socket = UdpSocket::bind(addr)?;
+        AsyncUdpSocket::new(socket, selector)
+    }
+
+    pub fn new(socket: UdpSocket, selector: Arc<IOSelector>) -> io::Result<AsyncUdpSocket> {
+        socket.set_nonblocking(true)?;
+        Ok(AsyncUdpSocket { socket, selector })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    pub fn send_to<'a>(&'a self, buf: &'a [u8], target: SocketAddr) -> SendTo<'a> {
+        SendTo {
+            socket: self,
+            buf,
+            target,
+        }
+    }
+
+    pub fn recv_from<'a>(&'a self, buf: &'a mut [u8]) -> RecvFrom<'a> {
+        RecvFrom { socket: self, buf }
+    }
+
+    pub fn poll_send_to(
+        &self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+        target: SocketAddr,
+    ) -> Poll<io::Result<usize>> {
+        poll_io(&self.selector, libc::EPOLLOUT, self.as_raw_fd(), cx, || {
+            self.socket.send_to(buf, target)
+        })
+    }
+
+    // 버퍼보다 큰 데이터그램은 잘린다.
+    pub fn poll_recv_from(
+        &self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr)>> {
+        poll_io(&self.selector, libc::EPOLLIN, self.as_raw_fd(), cx, || {
+            self.socket.recv_from(buf)
+        })
+    }
+}
+
+impl AsRawFd for AsyncUdpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.socket.as_raw_fd()
+    }
+}
+
+impl Drop for AsyncUdpSocket {
+    fn drop(&mut self) {
+        self.selector.unregister(self.socket.as_raw_fd());
+    }
+}
+
+pub struct SendTo<'a> {
+    socket: &'a AsyncUdpSocket,
+    buf: &'a [u8],
+    target: SocketAddr,
+}
+
+impl Future for SendTo<'_> {
+    type Output = io::Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.socket.poll_send_to(cx, self.buf, self.target)
+    }
+}
+
+pub struct RecvFrom<'a> {
+    socket: &'a AsyncUdpSocket,
+    buf: &'a mut [u8],
+}
+
+impl Future for RecvFrom<'_> {
+    type Output = io::Result<(usize, SocketAddr)>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        this.socket.poll_recv_from(cx, this.buf)
+    }
+}
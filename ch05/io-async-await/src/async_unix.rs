@@ -0,0 +1,231 @@
+use std::{
+    future::Future,
+    io,
+    net::Shutdown,
+    os::{
+        fd::{AsRawFd, RawFd},
+        unix::net::{SocketAddr, UnixListener, UnixStream},
+    },
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use crate::{
+    async_io::{poll_io, AsyncRead, AsyncWrite},
+    io_selector::IOSelector,
+    socket::{poll_connect, start_connect, unix_sockaddr},
+};
+
+pub struct AsyncUnixListener {
+    listener: UnixListener,
+    selector: Arc<IOSelector>,
+}
+
+impl AsyncUnixListener {
+    pub fn bind(
+        path: impl AsRef<Path>,
+        selector: Arc<IOSelector>,
+    ) -> io::Result<AsyncUnixListener> {
+        let listener = UnixListener::bind(path)?;
+        listener.set_nonblocking(true)?;
+
+        Ok(AsyncUnixListener { listener, selector })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    pub fn accept(&self) -> UnixAccept<'_> {
+        UnixAccept { listener: self }
+    }
+}
+
+impl Drop for AsyncUnixListener {
+    fn drop(&mut self) {
+        self.selector.unregister(self.listener.as_raw_fd());
+    }
+}
+
+pub struct UnixAccept<'a> {
+    listener: &'a AsyncUnixListener,
+}
+
+impl Future for UnixAccept<'_> {
+    type Output = io::Result<(AsyncUnixStream, SocketAddr)>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let listener = self.listener;
+        let fd = listener.listener.as_raw_fd();
+        poll_io(&listener.selector, libc::EPOLLIN, fd, cx, || {
+            let (stream, addr) = listener.listener.accept()?;
+            Ok((
+                AsyncUnixStream::new(stream, listener.selector.clone())?,
+                addr,
+            ))
+        })
+    }
+}
+
+pub struct AsyncUnixStream {
+    stream: UnixStream,
+    selector: Arc<IOSelector>,
+}
+
+impl AsyncUnixStream {
+    pub fn new(stream: UnixStream, selector: Arc<IOSelector>) -> io::Result<AsyncUnixStream> {
+        stream.set_nonblocking(true)?;
+        Ok(AsyncUnixStream { stream, selector })
+    }
+
+    pub fn connect(path: impl AsRef<Path>, selector: Arc<IOSelector>) -> UnixConnect {
+        UnixConnect {
+            path: path.as_ref().to_path_buf(),
+            stream: None,
+            selector,
+        }
+    }
+
+    // 연결된 한 쌍의 스트림을 만든다.
+    pub fn pair(selector: Arc<IOSelector>) -> io::Result<(AsyncUnixStream, AsyncUnixStream)> {
+        let (a, b) = UnixStream::pair()?;
+        Ok((
+            AsyncUnixStream::new(a, selector.clone())?,
+            AsyncUnixStream::new(b, selector)?,
+        ))
+    }
+
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.stream.peer_addr()
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.stream.local_addr()
+    }
+}
+
+impl AsRawFd for AsyncUnixStream {
+    fn as_raw_fd(&self) -> RawFd {
+        self.stream.as_raw_fd()
+    }
+}
+
+impl Drop for AsyncUnixStream {
+    fn drop(&mut self) {
+        self.selector.unregister(self.stream.as_raw_fd());
+    }
+}
+
+impl AsyncRead for &AsyncUnixStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.selector.poll_recv(self.stream.as_raw_fd(), cx, buf)
+    }
+}
+
+impl AsyncWrite for &AsyncUnixStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.selector.poll_send(self.stream.as_raw_fd(), cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.selector.poll_flush(self.stream.as_raw_fd(), cx)
+    }
+
+    // 아직 보내지 않은 데이터를 보낸 후에 닫는다.
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.selector.poll_flush(self.stream.as_raw_fd(), cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(self.stream.shutdown(Shutdown::Write)),
+            result => result,
+        }
+    }
+}
+
+impl AsyncRead for AsyncUnixStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut &*self).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for AsyncUnixStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut &*self).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut &*self).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut &*self).poll_shutdown(cx)
+    }
+}
+
+// 논블로킹 connect
+// 상대의 백로그가 가득 찬 경우에는 WouldBlock 에러를 반환한다.
+pub struct UnixConnect {
+    path: PathBuf,
+    stream: Option<UnixStream>,
+    selector: Arc<IOSelector>,
+}
+
+impl Future for UnixConnect {
+    type Output = io::Result<AsyncUnixStream>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let stream = match self.stream.take() {
+            Some(stream) => stream,
+            None => {
+                let (storage, len) = match unix_sockaddr(&self.path) {
+                    Ok(addr) => addr,
+                    Err(err) => return Poll::Ready(Err(err)),
+                };
+                match start_connect(libc::AF_UNIX, &storage, len) {
+                    Ok((fd, true)) => {
+                        let stream = UnixStream::from(fd);
+                        return Poll::Ready(AsyncUnixStream::new(stream, self.selector.clone()));
+                    }
+                    Ok((fd, false)) => UnixStream::from(fd),
+                    Err(err) => return Poll::Ready(Err(err)),
+                }
+            }
+        };
+
+        match poll_connect(&self.selector, stream.as_raw_fd(), cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(AsyncUnixStream::new(stream, self.selector.clone())),
+            Poll::Ready(Err(err)) => {
+                self.selector.unregister(stream.as_raw_fd());
+                Poll::Ready(Err(err))
+            }
+            Poll::Pending => {
+                self.stream = Some(stream);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for UnixConnect {
+    fn drop(&mut self) {
+        if let Some(stream) = &self.stream {
+            self.selector.unregister(stream.as_raw_fd());
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::{
+    io::{self, Read, Write},
+    net::{SocketAddr, TcpStream},
+    sync::{Arc, Barrier},
+    thread,
+    time::{Duration, Instant},
+};
+
+use clap::{builder::RangedU64ValueParser, Arg, Command};
+use io_async_await::{
+    async_io::{AsyncRead, AsyncWrite},
+    async_listener::AsyncListener,
+    excutor::Executor,
+    io_selector::{Backend, IOSelector},
+    sync::oneshot,
+};
+
+struct CommandArgs {
+    backends: Vec<Backend>,
+    num_client: usize,
+    num_request: usize,
+    size: usize,
+    num_worker: usize,
+}
+
+fn get_command_args() -> CommandArgs {
+    let matches = Command::new("echo_bench")
+        .arg(
+            Arg::new("backend")
+                .short('b')
+                .long("backend")
+                .value_name("BACKEND")
+                .num_args(1)
+                .help("Backend to measure (epoll, io_uring or all)")
+                // all이라면 None
+                .value_parser(|s: &str| match s {
+                    "all" => Ok(None),
+                    s => s.parse::<Backend>().map(Some),
+                })
+                .default_value("all"),
+        )
+        .arg(
+            Arg::new("num_client")
+                .short('c')
+                .long("num_client")
+                .value_name("NUM_CLIENT")
+                .num_args(1)
+                .help("Number of concurrent connections")
+                .value_parser(RangedU64ValueParser::<usize>::new().range(1..))
+                .default_value("8"),
+        )
+        .arg(
+            Arg::new("num_request")
+                .short('r')
+                .long("num_request")
+                .value_name("NUM_REQUEST")
+                .num_args(1)
+                .help("Number of round trips per connection")
+                .value_parser(RangedU64ValueParser::<usize>::new().range(1..))
+                .default_value("10000"),
+        )
+        .arg(
+            Arg::new("size")
+                .short('s')
+                .long("size")
+                .value_name("SIZE")
+                .num_args(1)
+                .help("Message size in bytes")
+                .value_parser(RangedU64ValueParser::<usize>::new().range(1..))
+                .default_value("64"),
+        )
+        .arg(
+            Arg::new("num_worker")
+                .short('w')
+                .long("num_worker")
+                .value_name("NUM_WORKER")
+                .num_args(1)
+                .help("Number of executor worker threads")
+                .value_parser(RangedU64ValueParser::<usize>::new().range(1..))
+                .default_value("4"),
+        )
+        .get_matches();
+
+    let backends = match matches.get_one::<Option<Backend>>("backend").unwrap() {
+        None => vec![Backend::Epoll, Backend::IoUring],
+        Some(backend) => vec![*backend],
+    };
+
+    CommandArgs {
+        backends,
+        num_client: matches.get_one("num_client").cloned().unwrap(),
+        num_request: matches.get_one("num_request").cloned().unwrap(),
+        size: matches.get_one("size").cloned().unwrap(),
+        num_worker: matches.get_one("num_worker").cloned().unwrap(),
+    }
+}
+
+// 각 클라이언트는 블로킹 소켓으로 메시지를 보내고 에코를 받을 때까지 기다린다.
+// 왕복마다 걸린 시간을 반환한다.
+fn run_clients(addr: SocketAddr, args: &CommandArgs) -> io::Result<(Duration, Vec<Duration>)> {
+    let barrier = Arc::new(Barrier::new(args.num_client + 1));
+    let clients = (0..args.num_client)
+        .map(|_| {
+            let barrier = barrier.clone();
+            let num_request = args.num_request;
+            let msg = vec![b'x'; args.size];
+            thread::spawn(move || -> io::Result<Vec<Duration>> {
+                let mut stream = TcpStream::connect(addr)?;
+                stream.set_nodelay(true)?;
+                let mut buf = vec![0; msg.len()];
+                let mut latencies = Vec::with_capacity(num_request);
+                barrier.wait();
+                for _ in 0..num_request {
+                    let t = Instant::now();
+                    stream.write_all(&msg)?;
+                    stream.read_exact(&mut buf)?;
+                    latencies.push(t.elapsed());
+                }
+                Ok(latencies)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    barrier.wait();
+    let t = Instant::now();
+    let mut latencies = Vec::new();
+    for client in clients {
+        latencies.extend(client.join().unwrap()?);
+    }
+    Ok((t.elapsed(), latencies))
+}
+
+fn bench(backend: Backend, args: &CommandArgs) -> io::Result<()> {
+    let executor = Executor::with_workers(args.num_worker);
+    let selector = IOSelector::with_backend(backend)?;
+    executor.attach_selector(selector.clone());
+
+    let listener = AsyncListener::listen("127.0.0.1:0", selector)?;
+    let addr = listener.local_addr()?;
+    let spawner = executor.get_spawner();
+    executor.get_spawner().spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            stream.set_nodelay(true).unwrap();
+            spawner.spawn(async move {
+                let mut buf = [0; 4096];
+                while let Ok(n) = stream.read(&mut buf).await {
+                    if n == 0 || stream.write_all(&buf[..n]).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+
+    // 클라이언트는 별도의 스레드에서 실행하고 그동안 워커를 돌린다.
+    let (tx, rx) = oneshot::channel();
+    let client_args = CommandArgs {
+        backends: Vec::new(),
+        ..*args
+    };
+    thread::spawn(move || {
+        let _ = tx.send(run_clients(addr, &client_args));
+    });
+    let result = executor.block_on(rx).expect("client thread panicked");
+    executor.shutdown_handle().shutdown();
+    let (elapsed, mut latencies) = result?;
+
+    latencies.sort();
+    let total = latencies.len();
+    let percentile = |p: usize| latencies[(total * p / 100).min(total - 1)];
+    println!(
+        "{:>8}: {:>10.0} req/s, p50 = {:?}, p99 = {:?}, elapsed = {:?}",
+        backend.to_string(),
+        total as f64 / elapsed.as_secs_f64(),
+        percentile(50),
+        percentile(99),
+        elapsed,
+    );
+
+    Ok(())
+}
+
+fn main() -> io::Result<()> {
+    let args = get_command_args();
+    println!(
+        "clients = {}, requests = {}, size = {}, workers = {}",
+        args.num_client, args.num_request, args.size, args.num_worker
+    );
+    for &backend in args.backends.iter() {
+        bench(backend, &args)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Condvar, Mutex},
+    task::{Context, Waker},
+    thread,
+    time::Duration,
+};
+
+use crate::join_handle::{task_future, JoinHandle};
+
+// 일이 없는 스레드가 종료될 때까지의 시간
+const KEEP_ALIVE: Duration = Duration::from_secs(10);
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct PoolState {
+    queue: VecDeque<Job>,
+    // 생성된 스레드 수
+    threads: usize,
+    // 작업을 기다리는 스레드 수
+    idle: usize,
+    shutdown: bool,
+}
+
+struct PoolInner {
+    state: Mutex<PoolState>,
+    cond: Condvar,
+    max_threads: usize,
+}
+
+impl PoolInner {
+    fn run(&self) {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(job) = state.queue.pop_front() {
+                drop(state);
+                job();
+                state = self.state.lock().unwrap();
+                continue;
+            }
+
+            if state.shutdown {
+                break;
+            }
+
+            state.idle += 1;
+            let (s, result) = self.cond.wait_timeout(state, KEEP_ALIVE).unwrap();
+            state = s;
+            state.idle -= 1;
+
+            if result.timed_out() && state.queue.is_empty() {
+                break;
+            }
+        }
+
+        state.threads -= 1;
+    }
+}
+
+// 디스크 IO와 같은 블로킹 처리를 워커 밖에서 실행하기 위한 스레드 풀
+// 스레드는 필요할 때 max_threads까지 생성되고 일이 없으면 종료된다.
+#[derive(Clone)]
+pub struct BlockingPool {
+    inner: Arc<PoolInner>,
+}
+
+impl BlockingPool {
+    pub fn new(max_threads: usize) -> Self {
+        assert!(max_threads > 0);
+        let inner = PoolInner {
+            state: Mutex::new(PoolState {
+                queue: VecDeque::new(),
+                threads: 0,
+                idle: 0,
+                shutdown: false,
+            }),
+            cond: Condvar::new(),
+            max_threads,
+        };
+        Self {
+            inner: Arc::new(inner),
+        }
+    }
+
+    pub fn max_threads(&self) -> usize {
+        self.inner.max_threads
+    }
+
+    // f를 스레드 풀에서 실행하고 결과를 JoinHandle로 반환한다.
+    // 실행이 시작되기 전이라면 abort로 취소할 수 있지만 실행 중인 f는 멈추지 않는다.
+    // 풀이 종료된 후에는 바로 취소된다.
+    pub fn spawn_blocking<F, T>(&self, f: F) -> JoinHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        // f는 한 번의 poll로 완료되므로 waker는 필요 없다.
+        let (mut future, join_handle) = task_future(async move { f() });
+        let join_handle = join_handle(Waker::noop().clone());
+        let job: Job = Box::new(move || {
+            let mut ctx = Context::from_waker(Waker::noop());
+            let _ = Pin::new(&mut future).poll(&mut ctx);
+        });
+
+        let mut state = self.inner.state.lock().unwrap();
+        if state.shutdown {
+            // 락을 해제한 후에 파기해서 JoinError::Cancelled를 전달한다.
+            drop(state);
+            drop(job);
+            return join_handle;
+        }
+
+        state.queue.push_back(job);
+        if state.queue.len() > state.idle && state.threads < self.inner.max_threads {
+            state.threads += 1;
+            let inner = self.inner.clone();
+            let spawned = thread::Builder::new()
+                .name("blocking".to_string())
+                .spawn(move || inner.run());
+            if spawned.is_err() {
+                // 스레드를 생성하지 못했다면 기존의 스레드에 맡긴다.
+                // 스레드가 하나도 없다면 실행할 수 없으므로 취소한다.
+                state.threads -= 1;
+                if state.threads == 0 {
+                    let job = state.queue.pop_back();
+                    drop(state);
+                    drop(job);
+                    return join_handle;
+                }
+                self.inner.cond.notify_one();
+            }
+        } else {
+            self.inner.cond.notify_one();
+        }
+
+        join_handle
+    }
+
+    // 대기 중인 작업을 취소하고 스레드를 종료한다.
+    // 실행 중인 작업은 완료될 때까지 계속된다.
+    pub fn shutdown(&self) {
+        let jobs = {
+            let mut state = self.inner.state.lock().unwrap();
+            state.shutdown = true;
+            self.inner.cond.notify_all();
+            state.queue.drain(..).collect::<Vec<_>>()
+        };
+        drop(jobs);
+    }
+
+    pub fn is_shutdown(&self) -> bool {
+        self.inner.state.lock().unwrap().shutdown
+    }
+}
@@ -0,0 +1,268 @@
+use std::{
+    future::Future,
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+// 완료된 후에도 결과를 보관해 두는 Future
+enum MaybeDone<F: Future> {
+    Pending(Pin<Box<F>>),
+    Done(F::Output),
+    Taken,
+}
+
+// Future는 Box에 고정되어 있고 결과는 고정해서 사용하지 않는다.
+impl<F: Future> Unpin for MaybeDone<F> {}
+
+impl<F: Future> MaybeDone<F> {
+    fn new(future: F) -> Self {
+        MaybeDone::Pending(Box::pin(future))
+    }
+
+    // 완료되었다면 true
+    fn poll(&mut self, cx: &mut Context<'_>) -> bool {
+        let MaybeDone::Pending(future) = self else {
+            return true;
+        };
+        match future.as_mut().poll(cx) {
+            Poll::Ready(v) => {
+                *self = MaybeDone::Done(v);
+                true
+            }
+            Poll::Pending => false,
+        }
+    }
+
+    fn take(&mut self) -> F::Output {
+        match mem::replace(self, MaybeDone::Taken) {
+            MaybeDone::Done(v) => v,
+            _ => panic!("future is not completed"),
+        }
+    }
+}
+
+// 두 Future가 모두 완료될 때까지 기다린다.
+pub fn join<A: Future, B: Future>(a: A, b: B) -> Join<A, B> {
+    Join {
+        a: MaybeDone::new(a),
+        b: MaybeDone::new(b),
+    }
+}
+
+pub struct Join<A: Future, B: Future> {
+    a: MaybeDone<A>,
+    b: MaybeDone<B>,
+}
+
+impl<A: Future, B: Future> Future for Join<A, B> {
+    type Output = (A::Output, B::Output);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let a = self.a.poll(cx);
+        let b = self.b.poll(cx);
+        if a && b {
+            Poll::Ready((self.a.take(), self.b.take()))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+// 모든 Future가 완료될 때까지 기다리고 결과를 같은 순서로 반환한다.
+pub fn join_all<I>(futures: I) -> JoinAll<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Future,
+{
+    JoinAll {
+        futures: futures.into_iter().map(MaybeDone::new).collect(),
+    }
+}
+
+pub struct JoinAll<F: Future> {
+    futures: Vec<MaybeDone<F>>,
+}
+
+impl<F: Future> Future for JoinAll<F> {
+    type Output = Vec<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut done = true;
+        for future in self.futures.iter_mut() {
+            done &= future.poll(cx);
+        }
+
+        if done {
+            Poll::Ready(self.futures.iter_mut().map(MaybeDone::take).collect())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+// 두 Future가 모두 Ok로 완료될 때까지 기다린다.
+// 한쪽이 Err가 되면 다른 쪽을 기다리지 않고 Err를 반환한다.
+pub fn try_join<A, B, T1, T2, E>(a: A, b: B) -> TryJoin<A, B>
+where
+    A: Future<Output = Result<T1, E>>,
+    B: Future<Output = Result<T2, E>>,
+{
+    TryJoin {
+        a: MaybeDone::new(a),
+        b: MaybeDone::new(b),
+    }
+}
+
+pub struct TryJoin<A: Future, B: Future> {
+    a: MaybeDone<A>,
+    b: MaybeDone<B>,
+}
+
+impl<A, B, T1, T2, E> Future for TryJoin<A, B>
+where
+    A: Future<Output = Result<T1, E>>,
+    B: Future<Output = Result<T2, E>>,
+{
+    type Output = Result<(T1, T2), E>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let a = self.a.poll(cx);
+        if matches!(self.a, MaybeDone::Done(Err(_))) {
+            return Poll::Ready(Err(self.a.take().err().unwrap()));
+        }
+        let b = self.b.poll(cx);
+        if matches!(self.b, MaybeDone::Done(Err(_))) {
+            return Poll::Ready(Err(self.b.take().err().unwrap()));
+        }
+
+        if a && b {
+            let a = self.a.take().ok().unwrap();
+            let b = self.b.take().ok().unwrap();
+            Poll::Ready(Ok((a, b)))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+// 모든 Future가 Ok로 완료될 때까지 기다린다.
+// 어느 하나가 Err가 되면 나머지를 기다리지 않고 Err를 반환한다.
+pub fn try_join_all<I, T, E>(futures: I) -> TryJoinAll<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Future<Output = Result<T, E>>,
+{
+    TryJoinAll {
+        futures: futures.into_iter().map(MaybeDone::new).collect(),
+    }
+}
+
+pub struct TryJoinAll<F: Future> {
+    futures: Vec<MaybeDone<F>>,
+}
+
+impl<F, T, E> Future for TryJoinAll<F>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    type Output = Result<Vec<T>, E>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut done = true;
+        for future in self.futures.iter_mut() {
+            done &= future.poll(cx);
+            if matches!(future, MaybeDone::Done(Err(_))) {
+                return Poll::Ready(Err(future.take().err().unwrap()));
+            }
+        }
+
+        if done {
+            Poll::Ready(self.futures.iter_mut().map(MaybeDone::take).collect())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+pub enum Either<A, B> {
+    Left(A),
+    Right(B),
+}
+
+// 먼저 완료된 쪽의 결과를 반환하고 다른 쪽은 파기한다.
+// 둘 다 완료 가능하다면 a가 우선된다.
+pub fn select<A: Future, B: Future>(a: A, b: B) -> Select<A, B> {
+    Select {
+        a: Some(Box::pin(a)),
+        b: Some(Box::pin(b)),
+    }
+}
+
+pub struct Select<A, B> {
+    a: Option<Pin<Box<A>>>,
+    b: Option<Pin<Box<B>>>,
+}
+
+impl<A: Future, B: Future> Future for Select<A, B> {
+    type Output = Either<A::Output, B::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let a = self.a.as_mut().expect("Select polled after completion");
+        if let Poll::Ready(v) = a.as_mut().poll(cx) {
+            self.a = None;
+            self.b = None;
+            return Poll::Ready(Either::Left(v));
+        }
+
+        let b = self.b.as_mut().expect("Select polled after completion");
+        if let Poll::Ready(v) = b.as_mut().poll(cx) {
+            self.a = None;
+            self.b = None;
+            return Poll::Ready(Either::Right(v));
+        }
+
+        Poll::Pending
+    }
+}
+
+// 먼저 완료된 Future의 결과와 그 인덱스를 반환하고 나머지는 파기한다.
+pub fn select_all<I>(futures: I) -> SelectAll<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Future,
+{
+    let futures = futures.into_iter().map(Box::pin).collect::<Vec<_>>();
+    assert!(
+        !futures.is_empty(),
+        "select_all requires at least one future"
+    );
+    SelectAll { futures }
+}
+
+pub struct SelectAll<F> {
+    futures: Vec<Pin<Box<F>>>,
+}
+
+impl<F: Future> Future for SelectAll<F> {
+    type Output = (F::Output, usize);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let ready =
+            self.futures
+                .iter_mut()
+                .enumerate()
+                .find_map(|(i, f)| match f.as_mut().poll(cx) {
+                    Poll::Ready(v) => Some((v, i)),
+                    Poll::Pending => None,
+                });
+
+        match ready {
+            Some(ready) => {
+                self.futures.clear();
+                Poll::Ready(ready)
+            }
+            None => Poll::Pending,
+        }
+    }
+}
@@ -1,52 +1,454 @@
 use std::{
+    cell::{Cell, RefCell, UnsafeCell},
+    collections::{HashMap, VecDeque},
     future::Future,
-    pin::Pin,
+    io,
+    pin::{pin, Pin},
+    rc::Rc,
     sync::{
-        mpsc::{self, Receiver, SyncSender},
-        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering},
+        Arc, Condvar, Mutex,
     },
-    task::{Context, Wake, Waker},
+    task::{Context, Poll, Wake, Waker},
+    thread,
 };
 
+use crate::{
+    blocking::BlockingPool,
+    io_selector::{copy_error, IOSelector},
+    join_handle::{task_future, JoinHandle},
+};
+
+// 태스크의 상태
+// IDLE: 대기 중, SCHEDULED: 큐에 들어 있음, RUNNING: 실행 중,
+// NOTIFIED: 실행 중에 깨워짐, COMPLETE: 완료
+const IDLE: u8 = 0;
+const SCHEDULED: u8 = 1;
+const RUNNING: u8 = 2;
+const NOTIFIED: u8 = 3;
+const COMPLETE: u8 = 4;
+
 struct Task {
+    id: usize,
+    state: AtomicU8,
     // 실행하는 코루틴
-    future: Mutex<Pin<Box<dyn Future<Output = ()> + Send + Sync>>>,
-    // Executor에 스케줄링하기 위한 채널
-    sender: SyncSender<Arc<Task>>,
+    // RUNNING 상태로 만든 스레드만 접근한다.
+    future: UnsafeCell<Option<Pin<Box<dyn Future<Output = ()> + Send>>>>,
+    // Executor에 스케줄링하기 위한 공유 상태
+    shared: Arc<Shared>,
+}
+
+// future는 상태 머신에 의해 한 번에 하나의 스레드에서만 접근된다.
+unsafe impl Sync for Task {}
+
+impl Task {
+    fn run(self: Arc<Self>) {
+        if self
+            .state
+            .compare_exchange(SCHEDULED, RUNNING, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return;
+        }
+
+        let waker = Waker::from(self.clone());
+        let mut ctx = Context::from_waker(&waker);
+        let future = unsafe { &mut *self.future.get() };
+        let poll = match future.as_mut() {
+            Some(f) => f.as_mut().poll(&mut ctx),
+            None => Poll::Ready(()),
+        };
+
+        if poll.is_ready() {
+            *future = None;
+            self.state.store(COMPLETE, Ordering::Release);
+            self.shared.tasks.lock().unwrap().remove(&self.id);
+            return;
+        }
+
+        // 실행 중에 깨워졌다면 다시 스케줄링
+        if self
+            .state
+            .compare_exchange(RUNNING, IDLE, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            self.state.store(SCHEDULED, Ordering::Release);
+            let shared = self.shared.clone();
+            shared.schedule(self);
+        }
+    }
 }
 
 impl Wake for Task {
     fn wake(self: Arc<Self>) {
-        let self0 = self.clone();
-        self.sender.send(self0).unwrap();
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        let mut state = self.state.load(Ordering::Acquire);
+        loop {
+            let next = match state {
+                IDLE => SCHEDULED,
+                RUNNING => NOTIFIED,
+                // 이미 스케줄링되었거나 완료됨
+                _ => return,
+            };
+
+            match self
+                .state
+                .compare_exchange(state, next, Ordering::AcqRel, Ordering::Acquire)
+            {
+                Ok(_) => break,
+                Err(s) => state = s,
+            }
+        }
+
+        if state == IDLE {
+            self.shared.schedule(self.clone());
+        }
+    }
+}
+
+// 워커 스레드 사이에서 공유하는 상태
+struct Shared {
+    // 워커 밖에서 스케줄된 태스크가 들어가는 전역 큐
+    injector: Mutex<VecDeque<Arc<Task>>>,
+    // 워커별 로컬 큐
+    locals: Vec<Mutex<VecDeque<Arc<Task>>>>,
+    // 잠들어 있는 워커 수
+    sleepers: Mutex<usize>,
+    cond: Condvar,
+    // 완료되지 않은 태스크
+    // 종료 시에 취소하기 위해서 보관한다.
+    tasks: Mutex<HashMap<usize, Arc<Task>>>,
+    next_id: AtomicUsize,
+    // 태스크를 실행 중이거나 찾는 중인 워커 수
+    active: AtomicUsize,
+    // 실행 중인 워커 수
+    live_workers: AtomicUsize,
+    // 워커를 멈춘다.
+    stop: AtomicBool,
+    shutdown: AtomicBool,
+    // 종료 시에 함께 멈출 IOSelector
+    selectors: Mutex<Vec<Arc<IOSelector>>>,
+    // 종료 시에 함께 멈출 블로킹 스레드 풀
+    pools: Mutex<Vec<BlockingPool>>,
+    // IOSelector에서 통지된 치명적인 에러
+    error: Mutex<Option<io::Error>>,
+}
+
+thread_local! {
+    // 현재 스레드가 워커라면 소속된 Shared의 주소와 워커 번호
+    static WORKER: Cell<Option<(*const Shared, usize)>> = const { Cell::new(None) };
+}
+
+impl Shared {
+    fn schedule(self: &Arc<Self>, task: Arc<Task>) {
+        // 워커 스레드에서 깨웠다면 자신의 로컬 큐에 넣는다.
+        match WORKER.get() {
+            Some((ptr, idx)) if ptr == Arc::as_ptr(self) => {
+                self.locals[idx].lock().unwrap().push_back(task);
+            }
+            _ => {
+                self.injector.lock().unwrap().push_back(task);
+            }
+        }
+
+        // 큐에 넣은 후에 락을 획득하므로 잠들기 직전의 워커도 놓치지 않는다.
+        let sleepers = self.sleepers.lock().unwrap();
+        if *sleepers > 0 {
+            self.cond.notify_one();
+        }
+    }
+
+    fn has_task(&self) -> bool {
+        !self.injector.lock().unwrap().is_empty()
+            || self.locals.iter().any(|q| !q.lock().unwrap().is_empty())
+    }
+
+    // 로컬 큐, 전역 큐, 다른 워커의 로컬 큐 순서로 태스크를 찾는다.
+    fn find_task(&self, idx: usize) -> Option<Arc<Task>> {
+        if let Some(task) = self.locals[idx].lock().unwrap().pop_front() {
+            return Some(task);
+        }
+
+        if let Some(task) = self.injector.lock().unwrap().pop_front() {
+            return Some(task);
+        }
+
+        self.steal(idx)
+    }
+
+    // 다른 워커의 로컬 큐에서 절반을 훔쳐 온다.
+    fn steal(&self, idx: usize) -> Option<Arc<Task>> {
+        let n = self.locals.len();
+        (1..n).map(|i| (idx + i) % n).find_map(|victim| {
+            let stolen = {
+                let mut q = self.locals[victim].lock().unwrap();
+                let len = q.len();
+                if len == 0 {
+                    return None;
+                }
+                q.split_off(len - len.div_ceil(2))
+            };
+
+            let mut stolen = stolen.into_iter();
+            let task = stolen.next();
+            self.locals[idx].lock().unwrap().extend(stolen);
+            task
+        })
+    }
+
+    fn run_worker(self: &Arc<Self>, idx: usize, until_idle: bool) {
+        WORKER.set(Some((Arc::as_ptr(self), idx)));
+        self.live_workers.fetch_add(1, Ordering::SeqCst);
+
+        loop {
+            if self.stop.load(Ordering::SeqCst) {
+                break;
+            }
+
+            // 큐에서 꺼내기 전에 증가시켜서 유휴 상태를 잘못 판정하지 않도록 한다.
+            self.active.fetch_add(1, Ordering::SeqCst);
+            if let Some(task) = self.find_task(idx) {
+                task.run();
+                self.active.fetch_sub(1, Ordering::SeqCst);
+                continue;
+            }
+            self.active.fetch_sub(1, Ordering::SeqCst);
+
+            // 실행할 태스크가 없으면 잠든다.
+            let mut sleepers = self.sleepers.lock().unwrap();
+            if self.stop.load(Ordering::SeqCst) {
+                break;
+            }
+            if self.has_task() {
+                continue;
+            }
+            if until_idle && self.active.load(Ordering::SeqCst) == 0 {
+                // 모든 워커가 할 일이 없으므로 멈춘다.
+                self.stop.store(true, Ordering::SeqCst);
+                self.cond.notify_all();
+                break;
+            }
+            *sleepers += 1;
+            sleepers = self.cond.wait(sleepers).unwrap();
+            *sleepers -= 1;
+        }
+
+        WORKER.set(None);
+
+        // 종료 중이라면 마지막 워커가 남은 태스크를 취소한다.
+        if self.live_workers.fetch_sub(1, Ordering::SeqCst) == 1
+            && self.shutdown.load(Ordering::SeqCst)
+        {
+            self.cancel_all();
+        }
+    }
+
+    // first번 이후의 워커 스레드를 생성하고 호출한 스레드에서 main을 실행한다.
+    fn run_workers<R>(
+        self: &Arc<Self>,
+        first: usize,
+        until_idle: bool,
+        main: impl FnOnce() -> R,
+    ) -> R {
+        self.stop
+            .store(self.shutdown.load(Ordering::SeqCst), Ordering::SeqCst);
+
+        thread::scope(|s| {
+            (first..self.locals.len()).for_each(|idx| {
+                thread::Builder::new()
+                    .name(format!("worker-{}", idx))
+                    .spawn_scoped(s, move || self.run_worker(idx, until_idle))
+                    .unwrap();
+            });
+
+            main()
+        })
+    }
+
+    fn shutdown(&self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        self.stop.store(true, Ordering::SeqCst);
+        {
+            let _sleepers = self.sleepers.lock().unwrap();
+            self.cond.notify_all();
+        }
+
+        self.selectors
+            .lock()
+            .unwrap()
+            .drain(..)
+            .for_each(|selector| selector.shutdown());
+
+        self.pools
+            .lock()
+            .unwrap()
+            .drain(..)
+            .for_each(|pool| pool.shutdown());
+
+        // 실행 중인 워커가 없다면 바로 취소한다.
+        if self.live_workers.load(Ordering::SeqCst) == 0 {
+            self.cancel_all();
+        }
+    }
+
+    // 남아 있는 태스크의 Future를 파기해서 JoinHandle에 JoinError::Cancelled를 전달한다.
+    // 워커가 실행 중이지 않을 때만 호출한다.
+    fn cancel_all(&self) {
+        let tasks = self
+            .tasks
+            .lock()
+            .unwrap()
+            .drain()
+            .map(|(_, task)| task)
+            .collect::<Vec<_>>();
+
+        let futures = tasks
+            .iter()
+            .map(|task| {
+                task.state.store(COMPLETE, Ordering::SeqCst);
+                unsafe { (*task.future.get()).take() }
+            })
+            .collect::<Vec<_>>();
+
+        self.injector.lock().unwrap().clear();
+        self.locals.iter().for_each(|q| q.lock().unwrap().clear());
+
+        // Future의 drop에서 spawn이나 wake를 호출할 수 있으므로 락을 해제한 후에 파기한다.
+        drop(futures);
     }
 }
 
 pub struct Executor {
-    sender: SyncSender<Arc<Task>>,
-    receiver: Receiver<Arc<Task>>,
+    shared: Arc<Shared>,
 }
 
 impl Executor {
+    // 워커 수는 사용 가능한 CPU 수
     pub fn new() -> Self {
-        let (sender, receiver) = mpsc::sync_channel(1024);
-        Self { sender, receiver }
+        let num_workers = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        Self::with_workers(num_workers)
+    }
+
+    pub fn with_workers(num_workers: usize) -> Self {
+        assert!(num_workers > 0);
+        let shared = Shared {
+            injector: Mutex::new(VecDeque::new()),
+            locals: (0..num_workers)
+                .map(|_| Mutex::new(VecDeque::new()))
+                .collect(),
+            sleepers: Mutex::new(0),
+            cond: Condvar::new(),
+            tasks: Mutex::new(HashMap::new()),
+            next_id: AtomicUsize::new(0),
+            active: AtomicUsize::new(0),
+            live_workers: AtomicUsize::new(0),
+            stop: AtomicBool::new(false),
+            shutdown: AtomicBool::new(false),
+            selectors: Mutex::new(Vec::new()),
+            pools: Mutex::new(Vec::new()),
+            error: Mutex::new(None),
+        };
+        Self {
+            shared: Arc::new(shared),
+        }
+    }
+
+    pub fn num_workers(&self) -> usize {
+        self.shared.locals.len()
     }
 
     pub fn get_spawner(&self) -> Spawner {
         Spawner {
-            sender: self.sender.clone(),
+            shared: self.shared.clone(),
         }
     }
 
-    pub fn run(&self) {
-        while let Ok(task) = self.receiver.recv() {
-            let mut future = task.future.lock().unwrap();
-            let waker = Waker::from(task.clone());
-            let mut ctx = Context::from_waker(&waker);
-            let _ = future.as_mut().poll(&mut ctx);
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        ShutdownHandle {
+            shared: self.shared.clone(),
+        }
+    }
+
+    // 종료 시에 IOSelector의 스레드도 함께 멈춘다.
+    // IOSelector가 치명적인 에러로 멈추면 Executor도 종료하고 run이 에러를 반환한다.
+    pub fn attach_selector(&self, selector: Arc<IOSelector>) {
+        let shared = Arc::downgrade(&self.shared);
+        selector.on_fatal_error(move |err| {
+            if let Some(shared) = shared.upgrade() {
+                shared.error.lock().unwrap().get_or_insert(copy_error(err));
+                shared.shutdown();
+            }
+        });
+
+        if self.shared.shutdown.load(Ordering::SeqCst) {
+            selector.shutdown();
+        } else {
+            self.shared.selectors.lock().unwrap().push(selector);
+        }
+    }
+
+    // 종료 시에 블로킹 스레드 풀의 대기 중인 작업도 취소한다.
+    pub fn attach_blocking_pool(&self, pool: BlockingPool) {
+        let mut pools = self.shared.pools.lock().unwrap();
+        if self.shared.shutdown.load(Ordering::SeqCst) {
+            drop(pools);
+            pool.shutdown();
+        } else {
+            pools.push(pool);
+        }
+    }
+
+    // 호출한 스레드를 0번 워커로 사용하고 나머지 워커는 스레드를 생성한다.
+    // shutdown이 호출될 때까지 반환하지 않는다.
+    pub fn run(&self) -> io::Result<()> {
+        self.shared
+            .run_workers(1, false, || self.shared.run_worker(0, false));
+        self.take_error()
+    }
+
+    // 실행 가능한 태스크가 없어질 때까지 실행한다.
+    // IO나 타이머를 기다리는 태스크는 취소하지 않고 남겨 둔다.
+    pub fn run_until_idle(&self) -> io::Result<()> {
+        self.shared
+            .run_workers(1, true, || self.shared.run_worker(0, true));
+        self.take_error()
+    }
+
+    fn take_error(&self) -> io::Result<()> {
+        match self.shared.error.lock().unwrap().take() {
+            Some(err) => Err(err),
+            None => Ok(()),
         }
     }
+
+    // future가 완료될 때까지 워커를 실행하고 결과를 반환한다.
+    // future는 호출한 스레드에서 실행된다.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        let mut future = pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut ctx = Context::from_waker(&waker);
+
+        self.shared.run_workers(0, false, || {
+            let output = loop {
+                if let Poll::Ready(v) = future.as_mut().poll(&mut ctx) {
+                    break v;
+                }
+                thread::park();
+            };
+
+            // 워커만 멈추고 남은 태스크는 다음 실행을 위해 남겨 둔다.
+            let _sleepers = self.shared.sleepers.lock().unwrap();
+            self.shared.stop.store(true, Ordering::SeqCst);
+            self.shared.cond.notify_all();
+
+            output
+        })
+    }
 }
 
 impl Default for Executor {
@@ -55,18 +457,229 @@ impl Default for Executor {
     }
 }
 
+impl Drop for Executor {
+    fn drop(&mut self) {
+        self.shared.shutdown();
+    }
+}
+
+struct ThreadWaker(thread::Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+// 다른 스레드나 태스크에서 Executor를 종료하기 위한 핸들
+#[derive(Clone)]
+pub struct ShutdownHandle {
+    shared: Arc<Shared>,
+}
+
+impl ShutdownHandle {
+    // 워커를 멈추고 남은 태스크를 취소한 후 연결된 IOSelector를 멈춘다.
+    pub fn shutdown(&self) {
+        self.shared.shutdown();
+    }
+
+    pub fn is_shutdown(&self) -> bool {
+        self.shared.shutdown.load(Ordering::SeqCst)
+    }
+}
+
+#[derive(Clone)]
 pub struct Spawner {
-    sender: SyncSender<Arc<Task>>,
+    shared: Arc<Shared>,
 }
 
 impl Spawner {
-    pub fn spawn(&self, future: impl Future<Output = ()> + Send + Sync + 'static) {
-        let future = Box::pin(future);
+    // 태스크의 패닉은 Executor를 멈추지 않고 JoinHandle에 JoinError::Panic으로 전달된다.
+    // Executor가 종료된 후에는 바로 취소된다.
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let (future, join_handle) = task_future(future);
         let task = Arc::new(Task {
-            future: Mutex::new(future),
-            sender: self.sender.clone(),
+            id: self.shared.next_id.fetch_add(1, Ordering::Relaxed),
+            state: AtomicU8::new(SCHEDULED),
+            future: UnsafeCell::new(Some(Box::pin(future))),
+            shared: self.shared.clone(),
         });
+        let join_handle = join_handle(Waker::from(task.clone()));
+
+        {
+            let mut tasks = self.shared.tasks.lock().unwrap();
+            if self.shared.shutdown.load(Ordering::SeqCst) {
+                drop(tasks);
+                task.state.store(COMPLETE, Ordering::SeqCst);
+                drop(unsafe { (*task.future.get()).take() });
+                return join_handle;
+            }
+            tasks.insert(task.id, task.clone());
+        }
+
+        self.shared.schedule(task);
+        join_handle
+    }
+}
+
+// 로컬 태스크를 깨우기 위한 waker
+// Future 자체는 LocalExecutor가 가지고 있으므로 다른 스레드에서 깨워도 안전하다.
+struct LocalWaker {
+    id: usize,
+    scheduled: AtomicBool,
+    queue: Arc<LocalQueue>,
+}
+
+impl Wake for LocalWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if !self.scheduled.swap(true, Ordering::AcqRel) {
+            self.queue.ready.lock().unwrap().push_back(self.id);
+            self.queue.cond.notify_one();
+        }
+    }
+}
+
+struct LocalQueue {
+    // 실행 가능한 태스크의 ID
+    ready: Mutex<VecDeque<usize>>,
+    cond: Condvar,
+}
+
+struct LocalTask {
+    future: Pin<Box<dyn Future<Output = ()>>>,
+    waker: Arc<LocalWaker>,
+}
+
+struct LocalInner {
+    tasks: RefCell<HashMap<usize, LocalTask>>,
+    next_id: Cell<usize>,
+    queue: Arc<LocalQueue>,
+}
+
+impl LocalInner {
+    fn spawn_local<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+
+        let (future, join_handle) = task_future(future);
+        let waker = Arc::new(LocalWaker {
+            id,
+            scheduled: AtomicBool::new(false),
+            queue: self.queue.clone(),
+        });
+        let join_handle = join_handle(Waker::from(waker.clone()));
+
+        self.tasks.borrow_mut().insert(
+            id,
+            LocalTask {
+                future: Box::pin(future),
+                waker: waker.clone(),
+            },
+        );
+        waker.wake();
+
+        join_handle
+    }
+}
+
+// Send가 아닌 Future를 호출한 스레드에서만 실행하는 Executor
+pub struct LocalExecutor {
+    inner: Rc<LocalInner>,
+}
+
+impl LocalExecutor {
+    pub fn new() -> Self {
+        let inner = LocalInner {
+            tasks: RefCell::new(HashMap::new()),
+            next_id: Cell::new(0),
+            queue: Arc::new(LocalQueue {
+                ready: Mutex::new(VecDeque::new()),
+                cond: Condvar::new(),
+            }),
+        };
+        Self {
+            inner: Rc::new(inner),
+        }
+    }
+
+    pub fn get_spawner(&self) -> LocalSpawner {
+        LocalSpawner {
+            inner: self.inner.clone(),
+        }
+    }
+
+    pub fn spawn_local<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        self.inner.spawn_local(future)
+    }
+
+    // 모든 태스크가 완료될 때까지 실행한다.
+    pub fn run(&self) {
+        loop {
+            let id = {
+                let mut ready = self.inner.queue.ready.lock().unwrap();
+                loop {
+                    if let Some(id) = ready.pop_front() {
+                        break id;
+                    }
+                    if self.inner.tasks.borrow().is_empty() {
+                        return;
+                    }
+                    ready = self.inner.queue.cond.wait(ready).unwrap();
+                }
+            };
+
+            // 실행 중에 spawn_local을 호출할 수 있도록 맵에서 꺼내서 실행한다.
+            let Some(mut task) = self.inner.tasks.borrow_mut().remove(&id) else {
+                continue;
+            };
+            task.waker.scheduled.store(false, Ordering::Release);
+
+            let waker = Waker::from(task.waker.clone());
+            let mut ctx = Context::from_waker(&waker);
+            if task.future.as_mut().poll(&mut ctx).is_pending() {
+                self.inner.tasks.borrow_mut().insert(id, task);
+            }
+        }
+    }
+}
+
+impl Default for LocalExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone)]
+pub struct LocalSpawner {
+    inner: Rc<LocalInner>,
+}
 
-        self.sender.send(task).unwrap();
+impl LocalSpawner {
+    pub fn spawn_local<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        self.inner.spawn_local(future)
     }
 }
@@ -0,0 +1,283 @@
+use std::{
+    future::Future,
+    io::{self, Read, Seek, SeekFrom, Write},
+    panic,
+    path::Path,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use crate::{
+    async_io::{AsyncRead, AsyncWrite},
+    blocking::BlockingPool,
+    join_handle::{JoinError, JoinHandle},
+};
+
+// 한 번의 읽기나 쓰기로 전송하는 최대 크기
+const MAX_BUF: usize = 64 * 1024;
+
+fn join_result<T>(result: Result<T, JoinError>) -> io::Result<T> {
+    match result {
+        Ok(v) => Ok(v),
+        Err(JoinError::Cancelled) => Err(io::Error::other("blocking pool is shut down")),
+        // 블로킹 처리의 패닉은 호출한 태스크로 전파한다.
+        Err(JoinError::Panic(p)) => panic::resume_unwind(p),
+    }
+}
+
+// 블로킹 스레드 풀에서 실행한 파일 조작의 결과를 기다리는 Future
+pub struct Blocking<T> {
+    handle: JoinHandle<io::Result<T>>,
+}
+
+impl<T> Future for Blocking<T> {
+    type Output = io::Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.handle).poll(cx) {
+            Poll::Ready(result) => Poll::Ready(join_result(result).and_then(|r| r)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+fn asyncify<T, F>(pool: &BlockingPool, f: F) -> Blocking<T>
+where
+    F: FnOnce() -> io::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    Blocking {
+        handle: pool.spawn_blocking(f),
+    }
+}
+
+pub fn read(path: impl AsRef<Path>, pool: &BlockingPool) -> Blocking<Vec<u8>> {
+    let path = path.as_ref().to_owned();
+    asyncify(pool, move || std::fs::read(path))
+}
+
+pub fn read_to_string(path: impl AsRef<Path>, pool: &BlockingPool) -> Blocking<String> {
+    let path = path.as_ref().to_owned();
+    asyncify(pool, move || std::fs::read_to_string(path))
+}
+
+pub fn write(
+    path: impl AsRef<Path>,
+    contents: impl Into<Vec<u8>>,
+    pool: &BlockingPool,
+) -> Blocking<()> {
+    let path = path.as_ref().to_owned();
+    let contents = contents.into();
+    asyncify(pool, move || std::fs::write(path, contents))
+}
+
+// 스레드 풀과 주고받는 버퍼
+#[derive(Default)]
+struct Buf {
+    data: Vec<u8>,
+    // 아직 반환하지 않은 읽기 데이터의 시작 위치
+    pos: usize,
+}
+
+impl Buf {
+    fn remaining(&self) -> usize {
+        self.data.len() - self.pos
+    }
+
+    fn copy_to(&mut self, dst: &mut [u8]) -> usize {
+        let n = self.remaining().min(dst.len());
+        dst[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+        self.pos += n;
+        n
+    }
+}
+
+enum Op {
+    Read(io::Result<usize>),
+    Write(io::Result<()>),
+}
+
+enum State {
+    Idle(Buf),
+    // 스레드 풀에서 읽기 또는 쓰기를 실행 중
+    Busy(JoinHandle<(Op, Buf)>),
+}
+
+// 읽기와 쓰기를 블로킹 스레드 풀에서 실행하는 파일
+// 쓰기는 스레드 풀에 넘긴 시점에 완료로 취급하므로 에러는 다음 조작이나 flush에서 반환된다.
+pub struct File {
+    std: Arc<std::fs::File>,
+    pool: BlockingPool,
+    state: State,
+}
+
+impl File {
+    pub fn open(
+        path: impl AsRef<Path>,
+        pool: &BlockingPool,
+    ) -> impl Future<Output = io::Result<File>> + Send + 'static {
+        let path = path.as_ref().to_owned();
+        let pool = pool.clone();
+        async move {
+            let std = asyncify(&pool, move || std::fs::File::open(path)).await?;
+            Ok(File::from_std(std, pool))
+        }
+    }
+
+    pub fn create(
+        path: impl AsRef<Path>,
+        pool: &BlockingPool,
+    ) -> impl Future<Output = io::Result<File>> + Send + 'static {
+        let path = path.as_ref().to_owned();
+        let pool = pool.clone();
+        async move {
+            let std = asyncify(&pool, move || std::fs::File::create(path)).await?;
+            Ok(File::from_std(std, pool))
+        }
+    }
+
+    pub fn from_std(std: std::fs::File, pool: BlockingPool) -> File {
+        File {
+            std: Arc::new(std),
+            pool,
+            state: State::Idle(Buf::default()),
+        }
+    }
+
+    // 쓰기 중인 데이터를 기다린 후에 디스크와 동기화한다.
+    pub async fn sync_all(&mut self) -> io::Result<()> {
+        self.flush().await?;
+        let std = self.std.clone();
+        asyncify(&self.pool, move || std.sync_all()).await
+    }
+
+    // 실행 중인 조작의 완료를 기다리고 결과를 반환한다.
+    fn poll_busy(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<Op>> {
+        let State::Busy(handle) = &mut self.state else {
+            unreachable!();
+        };
+        let (op, buf) = match Pin::new(handle).poll(cx) {
+            Poll::Ready(result) => match join_result(result) {
+                Ok(v) => v,
+                Err(err) => {
+                    self.state = State::Idle(Buf::default());
+                    return Poll::Ready(Err(err));
+                }
+            },
+            Poll::Pending => return Poll::Pending,
+        };
+        self.state = State::Idle(buf);
+        Poll::Ready(Ok(op))
+    }
+}
+
+impl AsyncRead for File {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        dst: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = &mut *self;
+        loop {
+            match &mut this.state {
+                State::Idle(buf) => {
+                    if buf.remaining() > 0 || dst.is_empty() {
+                        return Poll::Ready(Ok(buf.copy_to(dst)));
+                    }
+
+                    let mut buf = std::mem::take(buf);
+                    let len = dst.len().min(MAX_BUF);
+                    let std = this.std.clone();
+                    this.state = State::Busy(this.pool.spawn_blocking(move || {
+                        buf.data.resize(len, 0);
+                        buf.pos = 0;
+                        let result = loop {
+                            match (&*std).read(&mut buf.data) {
+                                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                                result => break result,
+                            }
+                        };
+                        buf.data.truncate(*result.as_ref().unwrap_or(&0));
+                        (Op::Read(result), buf)
+                    }));
+                }
+                State::Busy(_) => match this.poll_busy(cx) {
+                    Poll::Ready(Ok(Op::Read(Ok(0)))) => return Poll::Ready(Ok(0)),
+                    Poll::Ready(Ok(Op::Read(Ok(_)))) => continue,
+                    Poll::Ready(Ok(Op::Read(Err(err)))) => return Poll::Ready(Err(err)),
+                    // 이전 쓰기의 결과
+                    Poll::Ready(Ok(Op::Write(Ok(())))) => continue,
+                    Poll::Ready(Ok(Op::Write(Err(err)))) => return Poll::Ready(Err(err)),
+                    Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                    Poll::Pending => return Poll::Pending,
+                },
+            }
+        }
+    }
+}
+
+impl AsyncWrite for File {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        src: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = &mut *self;
+        loop {
+            match &mut this.state {
+                State::Idle(buf) => {
+                    if src.is_empty() {
+                        return Poll::Ready(Ok(0));
+                    }
+
+                    // 읽기에서 남은 데이터가 있다면 그만큼 위치를 되돌린 후에 쓴다.
+                    let seek = buf.remaining() as i64;
+                    let mut buf = std::mem::take(buf);
+                    buf.data.clear();
+                    buf.pos = 0;
+                    let n = src.len().min(MAX_BUF);
+                    buf.data.extend_from_slice(&src[..n]);
+
+                    let std = this.std.clone();
+                    this.state = State::Busy(this.pool.spawn_blocking(move || {
+                        let result = (|| {
+                            if seek > 0 {
+                                (&*std).seek(SeekFrom::Current(-seek))?;
+                            }
+                            (&*std).write_all(&buf.data)
+                        })();
+                        buf.data.clear();
+                        (Op::Write(result), buf)
+                    }));
+                    return Poll::Ready(Ok(n));
+                }
+                State::Busy(_) => match this.poll_busy(cx) {
+                    // 읽은 데이터는 다음 Idle에서 위치를 되돌린다.
+                    Poll::Ready(Ok(Op::Read(_))) => continue,
+                    Poll::Ready(Ok(Op::Write(Ok(())))) => continue,
+                    Poll::Ready(Ok(Op::Write(Err(err)))) => return Poll::Ready(Err(err)),
+                    Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                    Poll::Pending => return Poll::Pending,
+                },
+            }
+        }
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        match this.state {
+            State::Idle(_) => Poll::Ready(Ok(())),
+            State::Busy(_) => match this.poll_busy(cx) {
+                Poll::Ready(Ok(Op::Write(Err(err)))) => Poll::Ready(Err(err)),
+                Poll::Ready(Ok(_)) => Poll::Ready(Ok(())),
+                Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+                Poll::Pending => Poll::Pending,
+            },
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
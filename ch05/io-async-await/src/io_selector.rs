@@ -1,181 +1,329 @@
 use std::{
-    collections::{HashMap, VecDeque},
-    ffi::{c_int, c_void},
-    io,
-    os::fd::RawFd,
-    process,
-    sync::{Arc, Mutex},
-    task::Waker,
+    ffi::c_int,
+    fmt, io,
+    net::SocketAddr,
+    os::fd::{OwnedFd, RawFd},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+    time::Instant,
 };
 
-use libc::{epoll_ctl, epoll_event};
+use crate::timer::Timer;
 
-fn write_eventfd(fd: RawFd, n: usize) {
-    let ptr = &n as *const usize as *const u8;
-    unsafe {
-        let val = std::slice::from_raw_parts(ptr, std::mem::size_of_val(&n));
-        if libc::write(fd, val.as_ptr() as *const c_void, val.len()) == -1 {
-            libc::perror(c"libc::write".as_ptr());
-        };
-    };
-}
+mod epoll;
+mod uring;
 
 type EpollFlags = c_int;
 
-enum IOOps {
-    // epoll에 추가
-    Add(EpollFlags, RawFd, Waker),
-    // epoll에서 삭제
-    Remove(RawFd),
+// 통지 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerMode {
+    // 이벤트가 발생할 때마다 기다리는 방향만 다시 등록한다. (EPOLLONESHOT)
+    OneShot,
+    // 읽기와 쓰기를 한 번에 등록해 두고 상태가 변할 때마다 통지받는다. (EPOLLET)
+    Edge,
 }
 
-pub struct IOSelector {
-    // fd에서 waker
-    wakers: Mutex<HashMap<RawFd, Waker>>,
-    // IO 큐
-    queue: Mutex<VecDeque<IOOps>>,
-    // epoll의 fd
-    epfd: RawFd,
-    // eventfd의 fd
-    event: RawFd,
+// IOSelector의 구현
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    // epoll로 준비 상태를 기다린 후에 논블로킹 IO를 실행한다.
+    Epoll,
+    // io_uring에 IO를 제출하고 완료를 기다린다.
+    IoUring,
 }
 
-impl IOSelector {
-    pub fn new() -> Arc<Self> {
-        let epfd = unsafe {
-            let epfd = libc::epoll_create1(0);
-            if epfd == -1 {
-                libc::perror(c"libc::epoll_create1".as_ptr());
-                process::exit(-1);
-            }
-            epfd
-        };
-        let event = unsafe {
-            let event = libc::eventfd(0, 0);
-            if event == -1 {
-                libc::perror(c"libc::eventfd".as_ptr());
-                process::exit(-1);
-            }
-            event
-        };
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Backend::Epoll => write!(f, "epoll"),
+            Backend::IoUring => write!(f, "io_uring"),
+        }
+    }
+}
 
-        let s = IOSelector {
-            wakers: Mutex::new(HashMap::new()),
-            queue: Mutex::new(VecDeque::new()),
-            epfd,
-            event,
-        };
-        let result = Arc::new(s);
+impl FromStr for Backend {
+    type Err = io::Error;
 
-        let result_clone = result.clone();
-        std::thread::spawn(move || result_clone.select());
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "epoll" => Ok(Backend::Epoll),
+            "io_uring" | "uring" => Ok(Backend::IoUring),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unknown backend: {}", s),
+            )),
+        }
+    }
+}
 
-        result
+// io::Error는 Clone을 구현하지 않으므로 같은 내용의 에러를 만든다.
+pub(crate) fn copy_error(err: &io::Error) -> io::Error {
+    match err.raw_os_error() {
+        Some(code) => io::Error::from_raw_os_error(code),
+        None => io::Error::new(err.kind(), err.to_string()),
     }
+}
 
-    fn add_event(
-        &self,
-        flag: EpollFlags,
-        fd: RawFd,
-        waker: Waker,
-        wakers: &mut HashMap<RawFd, Waker>,
-    ) {
-        let epoll_add = libc::EPOLL_CTL_ADD;
-        let epoll_mod = libc::EPOLL_CTL_MOD;
-        let epoll_one = libc::EPOLLONESHOT;
+type ErrorHandler = Box<dyn Fn(&io::Error) + Send + Sync>;
 
-        let mut ev = epoll_event {
-            events: flag as u32 | epoll_one as u32,
-            u64: fd as u64,
-        };
+// 백엔드에 공통인 종료 상태
+struct Status {
+    // shutdown이 호출되었거나 리액터 스레드가 멈췄다면 true
+    closed: AtomicBool,
+    // 리액터 스레드를 멈추게 한 에러
+    fatal: Mutex<Option<io::Error>>,
+    // 치명적인 에러를 통지받을 함수
+    error_handlers: Mutex<Vec<ErrorHandler>>,
+}
 
-        unsafe {
-            if epoll_ctl(self.epfd, epoll_add, fd, &mut ev) == -1 {
-                match io::Error::last_os_error().kind() {
-                    io::ErrorKind::AlreadyExists => {
-                        // 이미 추가되어 있는 경우에는 재설정
-                        if epoll_ctl(self.epfd, epoll_mod, fd, &mut ev) == -1 {
-                            libc::perror(c"libc::epoll_ctl".as_ptr());
-                            process::exit(-1);
-                        }
-                    }
-                    err => panic!("epoll_ctl: {}", err),
-                }
-            }
+impl Status {
+    fn new() -> Self {
+        Status {
+            closed: AtomicBool::new(false),
+            fatal: Mutex::new(None),
+            error_handlers: Mutex::new(Vec::new()),
         }
+    }
 
-        assert!(!wakers.contains_key(&fd));
-        wakers.insert(fd, waker);
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
     }
 
-    fn rm_event(&self, fd: RawFd, wakers: &mut HashMap<RawFd, Waker>) {
-        let epoll_del = libc::EPOLL_CTL_DEL;
+    // 처음으로 닫았다면 true
+    fn close(&self) -> bool {
+        !self.closed.swap(true, Ordering::SeqCst)
+    }
 
-        let mut ev = epoll_event {
-            events: 0,
-            u64: fd as u64,
+    // 닫힌 후에 IO가 반환할 에러
+    fn error(&self) -> Option<io::Error> {
+        if !self.is_closed() {
+            return None;
+        }
+        match &*self.fatal.lock().unwrap() {
+            Some(err) => Some(copy_error(err)),
+            None => Some(io::Error::other("IOSelector is shut down")),
+        }
+    }
+
+    // 에러 핸들러에 치명적인 에러를 통지한다.
+    // 기다리는 태스크는 호출하기 전에 백엔드가 깨운다.
+    fn fail(&self, err: io::Error) {
+        self.closed.store(true, Ordering::SeqCst);
+        let handlers = {
+            let mut handlers = self.error_handlers.lock().unwrap();
+            *self.fatal.lock().unwrap() = Some(copy_error(&err));
+            std::mem::take(&mut *handlers)
         };
+        handlers.iter().for_each(|handler| handler(&err));
+    }
 
-        unsafe {
-            epoll_ctl(self.epfd, epoll_del, fd, &mut ev);
+    fn on_fatal_error(&self, handler: impl Fn(&io::Error) + Send + Sync + 'static) {
+        let mut handlers = self.error_handlers.lock().unwrap();
+        let fatal = self.fatal.lock().unwrap();
+        match &*fatal {
+            Some(err) => {
+                let err = copy_error(err);
+                drop(fatal);
+                drop(handlers);
+                handler(&err);
+            }
+            None => handlers.push(Box::new(handler)),
         }
-        wakers.remove(&fd);
     }
+}
 
-    fn select(&self) {
-        let epoll_in = libc::EPOLLIN;
-        let epoll_add = libc::EPOLL_CTL_ADD;
+// IOSelector의 백엔드
+// 준비 상태의 통지와 소켓 IO를 각각의 방식으로 구현한다.
+trait Reactor: Send + Sync {
+    fn status(&self) -> &Status;
 
-        let mut ev = libc::epoll_event {
-            events: epoll_in as u32,
-            u64: self.event as u64,
-        };
-        unsafe {
-            if libc::epoll_ctl(self.epfd, epoll_add, self.event, &mut ev) == -1 {
-                libc::perror(c"libc::epoll_ctl".as_ptr());
-                process::exit(-1);
-            }
+    fn register(&self, flags: EpollFlags, fd: RawFd, waker: Waker, mode: TriggerMode);
+
+    fn unregister(&self, fd: RawFd);
+
+    fn add_timer(&self, deadline: Instant, timer: Arc<Timer>);
+
+    // 기한 전에 버려진 타이머를 제거한다.
+    fn cancel_timer(&self, timer: &Arc<Timer>);
 
-            let mut events = vec![epoll_event { events: 0, u64: 0 }; 1024];
-            loop {
-                let nfds = libc::epoll_wait(self.epfd, events.as_mut_ptr(), 1024, -1);
-                if nfds == -1 {
-                    break;
+    // fd의 등록 중에 발생한 에러를 꺼낸다.
+    fn take_fd_error(&self, fd: RawFd) -> Option<io::Error>;
+
+    // 리액터 스레드를 멈춘다.
+    fn shutdown(&self);
+
+    fn poll_recv(&self, fd: RawFd, cx: &mut Context<'_>, buf: &mut [u8])
+        -> Poll<io::Result<usize>>;
+
+    fn poll_send(&self, fd: RawFd, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>>;
+
+    // 아직 완료되지 않은 송신을 기다린다.
+    fn poll_flush(&self, fd: RawFd, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+
+    fn poll_accept(
+        &self,
+        fd: RawFd,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<(OwnedFd, SocketAddr)>>;
+}
+
+fn take_error<R: Reactor + ?Sized>(reactor: &R, fd: RawFd) -> Option<io::Error> {
+    reactor
+        .status()
+        .error()
+        .or_else(|| reactor.take_fd_error(fd))
+}
+
+// 논블로킹 IO를 실행하고 WouldBlock이면 준비될 때까지 기다리도록 등록한다.
+// 등록 중에 발생한 에러나 IOSelector의 에러는 IO의 에러로 반환한다.
+fn poll_ready<R: Reactor + ?Sized, T>(
+    reactor: &R,
+    flags: EpollFlags,
+    fd: RawFd,
+    cx: &mut Context<'_>,
+    mut f: impl FnMut() -> io::Result<T>,
+) -> Poll<io::Result<T>> {
+    loop {
+        match f() {
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                if let Some(err) = take_error(reactor, fd) {
+                    return Poll::Ready(Err(err));
                 }
-                let mut t = self.wakers.lock().unwrap();
-                events[..nfds as usize].iter().for_each(|event| {
-                    if event.u64 == self.event as u64 {
-                        let mut q = self.queue.lock().unwrap();
-                        while let Some(op) = q.pop_front() {
-                            match op {
-                                IOOps::Add(flag, fd, waker) => {
-                                    self.add_event(flag, fd, waker, &mut t);
-                                }
-                                IOOps::Remove(fd) => {
-                                    self.rm_event(fd, &mut t);
-                                }
-                            }
-                        }
-                    } else {
-                        let data = event.u64 as i32;
-                        let waker = t.remove(&data).unwrap();
-                        waker.wake_by_ref();
-                    }
-                });
+                reactor.register(flags, fd, cx.waker().clone(), TriggerMode::OneShot);
+                return Poll::Pending;
             }
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            result => return Poll::Ready(result),
         }
     }
+}
+
+pub struct IOSelector {
+    reactor: Arc<dyn Reactor>,
+    backend: Backend,
+}
+
+impl IOSelector {
+    pub fn new() -> io::Result<Arc<Self>> {
+        IOSelector::with_backend(Backend::Epoll)
+    }
+
+    // io_uring을 사용할 수 없는 커널에서는 IoUring을 지정하면 에러가 된다.
+    pub fn with_backend(backend: Backend) -> io::Result<Arc<Self>> {
+        let reactor: Arc<dyn Reactor> = match backend {
+            Backend::Epoll => epoll::EpollReactor::new()?,
+            Backend::IoUring => uring::UringReactor::new()?,
+        };
+        Ok(Arc::new(IOSelector { reactor, backend }))
+    }
+
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
 
     // 파일 디스크립터 등록용 함수
+    // flags에 EPOLLIN과 EPOLLOUT을 지정하면 각각 읽기와 쓰기 waker로 등록된다.
+    // 종료 후에는 아무것도 하지 않는다.
     pub fn register(&self, flags: EpollFlags, fd: RawFd, waker: Waker) {
-        let mut q = self.queue.lock().unwrap();
-        q.push_back(IOOps::Add(flags, fd, waker));
-        write_eventfd(self.event, 1);
+        self.register_with_mode(flags, fd, waker, TriggerMode::OneShot);
+    }
+
+    pub fn register_with_mode(
+        &self,
+        flags: EpollFlags,
+        fd: RawFd,
+        waker: Waker,
+        mode: TriggerMode,
+    ) {
+        self.reactor.register(flags, fd, waker, mode);
     }
 
     pub fn unregister(&self, fd: RawFd) {
-        let mut q = self.queue.lock().unwrap();
-        q.push_back(IOOps::Remove(fd));
-        write_eventfd(self.event, 1);
+        self.reactor.unregister(fd);
+    }
+
+    // deadline에 timer를 깨운다.
+    pub(crate) fn add_timer(&self, deadline: Instant, timer: Arc<Timer>) {
+        self.reactor.add_timer(deadline, timer);
+    }
+
+    pub(crate) fn cancel_timer(&self, timer: &Arc<Timer>) {
+        self.reactor.cancel_timer(timer);
+    }
+
+    // fd의 등록 중에 발생한 에러를 꺼낸다.
+    // 리액터 스레드가 멈춘 후에는 항상 에러를 반환한다.
+    pub fn take_error(&self, fd: RawFd) -> Option<io::Error> {
+        take_error(&*self.reactor, fd)
+    }
+
+    // 리액터 스레드가 치명적인 에러로 멈췄을 때 호출할 함수를 등록한다.
+    // 이미 멈췄다면 바로 호출한다.
+    pub fn on_fatal_error(&self, handler: impl Fn(&io::Error) + Send + Sync + 'static) {
+        self.reactor.status().on_fatal_error(handler);
+    }
+
+    // 리액터 스레드를 멈춘다.
+    pub fn shutdown(&self) {
+        self.reactor.shutdown();
+    }
+
+    pub fn is_shutdown(&self) -> bool {
+        self.reactor.status().is_closed()
+    }
+
+    pub(crate) fn poll_ready<T>(
+        &self,
+        flags: EpollFlags,
+        fd: RawFd,
+        cx: &mut Context<'_>,
+        f: impl FnMut() -> io::Result<T>,
+    ) -> Poll<io::Result<T>> {
+        poll_ready(&*self.reactor, flags, fd, cx, f)
+    }
+
+    // 스트림 소켓의 IO
+    // io_uring에서는 커널이 IO를 완료한 후에 깨운다.
+    pub(crate) fn poll_recv(
+        &self,
+        fd: RawFd,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.reactor.poll_recv(fd, cx, buf)
+    }
+
+    // io_uring에서는 데이터를 복사한 시점에 완료로 하고 송신 결과는 다음 쓰기나 flush에서 반환한다.
+    pub(crate) fn poll_send(
+        &self,
+        fd: RawFd,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.reactor.poll_send(fd, cx, buf)
+    }
+
+    pub(crate) fn poll_flush(&self, fd: RawFd, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.reactor.poll_flush(fd, cx)
+    }
+
+    pub(crate) fn poll_accept(
+        &self,
+        fd: RawFd,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<(OwnedFd, SocketAddr)>> {
+        self.reactor.poll_accept(fd, cx)
+    }
+}
+
+// 리액터 스레드는 백엔드만 참조하므로 마지막 IOSelector가 파기되면 멈춘다.
+impl Drop for IOSelector {
+    fn drop(&mut self) {
+        self.reactor.shutdown();
     }
 }
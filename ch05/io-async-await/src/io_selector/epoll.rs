@@ -0,0 +1,515 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    ffi::{c_int, c_void},
+    io,
+    net::SocketAddr,
+    os::fd::{OwnedFd, RawFd},
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    time::Instant,
+};
+
+use libc::{epoll_ctl, epoll_event};
+
+use super::{poll_ready, EpollFlags, Reactor, Status, TriggerMode};
+use crate::{socket, timer::Timer};
+
+fn write_eventfd(fd: RawFd, n: usize) {
+    let ptr = &n as *const usize as *const u8;
+    unsafe {
+        let val = std::slice::from_raw_parts(ptr, std::mem::size_of_val(&n));
+        if libc::write(fd, val.as_ptr() as *const c_void, val.len()) == -1 {
+            libc::perror(c"libc::write".as_ptr());
+        };
+    };
+}
+
+fn read_eventfd(fd: RawFd) {
+    let mut n = 0_u64;
+    unsafe {
+        libc::read(
+            fd,
+            &mut n as *mut u64 as *mut c_void,
+            std::mem::size_of_val(&n),
+        );
+    }
+}
+
+// fd별로 기다리고 있는 읽기와 쓰기
+#[derive(Default)]
+struct Interest {
+    read: Option<Waker>,
+    write: Option<Waker>,
+    edge: bool,
+    // 엣지 트리거에서 기다리는 waker 없이 도착한 이벤트
+    // 다음 등록 시에 바로 깨운다.
+    read_ready: bool,
+    write_ready: bool,
+    // epoll에 추가되어 있다면 true
+    added: bool,
+    // epoll_ctl에서 발생한 에러
+    // 다음 IO에서 take_error로 꺼내서 반환한다.
+    error: Option<io::Error>,
+}
+
+impl Interest {
+    fn events(&self) -> u32 {
+        let events = if self.edge {
+            libc::EPOLLIN | libc::EPOLLRDHUP | libc::EPOLLOUT | libc::EPOLLET
+        } else {
+            let mut events = libc::EPOLLONESHOT;
+            if self.read.is_some() {
+                events |= libc::EPOLLIN | libc::EPOLLRDHUP;
+            }
+            if self.write.is_some() {
+                events |= libc::EPOLLOUT;
+            }
+            events
+        };
+        events as u32
+    }
+
+    fn is_waiting(&self) -> bool {
+        self.read.is_some() || self.write.is_some()
+    }
+
+    fn set_error(&mut self, err: io::Error) {
+        self.error = Some(err);
+        // 기다리고 있는 태스크가 에러를 받을 수 있도록 깨운다.
+        if let Some(waker) = self.read.take() {
+            waker.wake();
+        }
+        if let Some(waker) = self.write.take() {
+            waker.wake();
+        }
+    }
+}
+
+enum IOOps {
+    // epoll에 추가
+    Add(EpollFlags, RawFd, Waker, TriggerMode),
+    // epoll에서 삭제
+    Remove(RawFd),
+    // 타이머 추가
+    Timer(Instant, Arc<Timer>),
+    // select 스레드 종료
+    Shutdown,
+}
+
+// 타이머 힙의 요소
+// 기한이 같으면 먼저 등록한 타이머가 먼저 만료된다.
+struct TimerEntry {
+    deadline: Instant,
+    seq: u64,
+    timer: Arc<Timer>,
+}
+
+impl PartialEq for TimerEntry {
+    fn eq(&self, other: &Self) -> bool {
+        (self.deadline, self.seq) == (other.deadline, other.seq)
+    }
+}
+
+impl Eq for TimerEntry {}
+
+impl PartialOrd for TimerEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TimerEntry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.deadline, self.seq).cmp(&(other.deadline, other.seq))
+    }
+}
+
+// 가장 빠른 타이머까지 남은 시간을 epoll_wait의 타임아웃(밀리초)으로 변환한다.
+// 일찍 깨어나지 않도록 올림한다.
+fn epoll_timeout(timers: &BinaryHeap<Reverse<TimerEntry>>) -> c_int {
+    match timers.peek() {
+        None => -1,
+        Some(Reverse(entry)) => {
+            let dur = entry.deadline.saturating_duration_since(Instant::now());
+            let ms = dur.as_nanos().div_ceil(1_000_000);
+            ms.min(c_int::MAX as u128) as c_int
+        }
+    }
+}
+
+// epoll과 eventfd의 제어 큐로 구현한 백엔드
+pub(super) struct EpollReactor {
+    // fd에서 waker
+    wakers: Mutex<HashMap<RawFd, Interest>>,
+    // IO 큐
+    queue: Mutex<VecDeque<IOOps>>,
+    // epoll의 fd
+    epfd: RawFd,
+    // eventfd의 fd
+    event: RawFd,
+    status: Status,
+}
+
+impl EpollReactor {
+    pub(super) fn new() -> io::Result<Arc<Self>> {
+        let epfd = unsafe { libc::epoll_create1(0) };
+        if epfd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        let event = unsafe { libc::eventfd(0, 0) };
+        if event == -1 {
+            let err = io::Error::last_os_error();
+            unsafe { libc::close(epfd) };
+            return Err(err);
+        }
+
+        // 이후에 에러가 발생하면 drop에서 fd를 닫는다.
+        let s = EpollReactor {
+            wakers: Mutex::new(HashMap::new()),
+            queue: Mutex::new(VecDeque::new()),
+            epfd,
+            event,
+            status: Status::new(),
+        };
+        s.ctl(libc::EPOLL_CTL_ADD, event, libc::EPOLLIN as u32)?;
+        let result = Arc::new(s);
+
+        let result_clone = result.clone();
+        std::thread::Builder::new()
+            .name("io-selector".to_string())
+            .spawn(move || result_clone.select())?;
+
+        Ok(result)
+    }
+
+    fn add_event(
+        &self,
+        flag: EpollFlags,
+        fd: RawFd,
+        waker: Waker,
+        mode: TriggerMode,
+        wakers: &mut HashMap<RawFd, Interest>,
+    ) {
+        let interest = wakers.entry(fd).or_default();
+        let edge = mode == TriggerMode::Edge;
+        let mode_changed = interest.edge != edge;
+        interest.edge = edge;
+
+        if flag & libc::EPOLLIN != 0 {
+            if edge && interest.read_ready {
+                interest.read_ready = false;
+                waker.wake_by_ref();
+            } else {
+                interest.read = Some(waker.clone());
+            }
+        }
+        if flag & libc::EPOLLOUT != 0 {
+            if edge && interest.write_ready {
+                interest.write_ready = false;
+                waker.wake_by_ref();
+            } else {
+                interest.write = Some(waker);
+            }
+        }
+
+        // 엣지 트리거는 한 번만 등록하면 된다.
+        if edge && interest.added && !mode_changed {
+            return;
+        }
+        if !edge && !interest.is_waiting() {
+            return;
+        }
+
+        let events = interest.events();
+        let op = if interest.added {
+            libc::EPOLL_CTL_MOD
+        } else {
+            libc::EPOLL_CTL_ADD
+        };
+        match self.ctl(op, fd, events) {
+            Ok(()) => interest.added = true,
+            Err(err) => interest.set_error(err),
+        }
+    }
+
+    fn ctl(&self, op: c_int, fd: RawFd, events: u32) -> io::Result<()> {
+        let mut ev = epoll_event {
+            events,
+            u64: fd as u64,
+        };
+
+        unsafe {
+            if epoll_ctl(self.epfd, op, fd, &mut ev) == -1 {
+                let err = io::Error::last_os_error();
+                if op != libc::EPOLL_CTL_ADD || err.kind() != io::ErrorKind::AlreadyExists {
+                    return Err(err);
+                }
+
+                // 이미 추가되어 있는 경우에는 재설정
+                if epoll_ctl(self.epfd, libc::EPOLL_CTL_MOD, fd, &mut ev) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn rm_event(&self, fd: RawFd, wakers: &mut HashMap<RawFd, Interest>) {
+        let epoll_del = libc::EPOLL_CTL_DEL;
+
+        let mut ev = epoll_event {
+            events: 0,
+            u64: fd as u64,
+        };
+
+        unsafe {
+            epoll_ctl(self.epfd, epoll_del, fd, &mut ev);
+        }
+        wakers.remove(&fd);
+    }
+
+    // 발생한 방향의 waker를 깨우고 원샷이라면 남은 방향을 다시 등록한다.
+    fn dispatch(&self, event: &epoll_event, wakers: &mut HashMap<RawFd, Interest>) {
+        let fd = event.u64 as RawFd;
+        let Some(interest) = wakers.get_mut(&fd) else {
+            return;
+        };
+
+        let events = event.events as c_int;
+        let hup = libc::EPOLLHUP | libc::EPOLLERR;
+        if events & (libc::EPOLLIN | libc::EPOLLRDHUP | hup) != 0 {
+            match interest.read.take() {
+                Some(waker) => waker.wake(),
+                None => interest.read_ready = interest.edge,
+            }
+        }
+        if events & (libc::EPOLLOUT | hup) != 0 {
+            match interest.write.take() {
+                Some(waker) => waker.wake(),
+                None => interest.write_ready = interest.edge,
+            }
+        }
+
+        if !interest.edge && interest.is_waiting() {
+            let events = interest.events();
+            if let Err(err) = self.ctl(libc::EPOLL_CTL_MOD, fd, events) {
+                interest.set_error(err);
+            }
+        }
+    }
+
+    fn select(&self) {
+        // 시그널은 signalfd로 받으므로 이 스레드에는 배송되지 않도록 한다.
+        unsafe {
+            let mut set: libc::sigset_t = std::mem::zeroed();
+            libc::sigfillset(&mut set);
+            libc::pthread_sigmask(libc::SIG_BLOCK, &set, std::ptr::null_mut());
+        }
+
+        let mut timers = BinaryHeap::new();
+        if let Err(err) = self.select_loop(&mut timers) {
+            self.fail(err);
+        }
+
+        // 태스크를 참조하는 waker를 파기한다.
+        self.wakers.lock().unwrap().clear();
+        let queued = self
+            .queue
+            .lock()
+            .unwrap()
+            .drain(..)
+            .filter_map(|op| match op {
+                IOOps::Timer(_, timer) => Some(timer),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // 더 이상 기한을 확인하지 않으므로 남은 타이머를 모두 깨운다.
+        // Sleep은 종료를 확인하고 완료한다.
+        timers
+            .into_iter()
+            .map(|Reverse(entry)| entry.timer)
+            .chain(queued)
+            .for_each(|timer| timer.fire());
+    }
+
+    fn select_loop(&self, timers: &mut BinaryHeap<Reverse<TimerEntry>>) -> io::Result<()> {
+        unsafe {
+            let mut events = vec![epoll_event { events: 0, u64: 0 }; 1024];
+            let mut seq = 0;
+            // 마지막으로 정리한 후에 남은 타이머 수
+            let mut live = 0;
+            let mut shutdown = false;
+            while !shutdown {
+                let timeout = epoll_timeout(timers);
+                let nfds = libc::epoll_wait(self.epfd, events.as_mut_ptr(), 1024, timeout);
+                if nfds == -1 {
+                    let err = io::Error::last_os_error();
+                    if err.kind() == io::ErrorKind::Interrupted {
+                        continue;
+                    }
+                    return Err(err);
+                }
+                let mut t = self.wakers.lock().unwrap();
+                events[..nfds as usize].iter().for_each(|event| {
+                    if event.u64 == self.event as u64 {
+                        read_eventfd(self.event);
+                        let mut q = self.queue.lock().unwrap();
+                        while let Some(op) = q.pop_front() {
+                            match op {
+                                IOOps::Add(flag, fd, waker, mode) => {
+                                    self.add_event(flag, fd, waker, mode, &mut t);
+                                }
+                                IOOps::Remove(fd) => {
+                                    self.rm_event(fd, &mut t);
+                                }
+                                IOOps::Timer(deadline, timer) => {
+                                    timers.push(Reverse(TimerEntry {
+                                        deadline,
+                                        seq,
+                                        timer,
+                                    }));
+                                    seq += 1;
+                                }
+                                IOOps::Shutdown => shutdown = true,
+                            }
+                        }
+                    } else {
+                        self.dispatch(event, &mut t);
+                    }
+                });
+                drop(t);
+
+                // 기한이 지난 타이머를 깨운다.
+                let now = Instant::now();
+                while let Some(Reverse(entry)) = timers.peek() {
+                    if entry.deadline > now {
+                        break;
+                    }
+                    let Reverse(entry) = timers.pop().unwrap();
+                    entry.timer.fire();
+                }
+
+                // 취소된 타이머를 제거한다.
+                // 맨 앞의 것은 바로 버리고, 나머지는 힙이 정리 후의 두 배를 넘었을 때 모아서 버린다.
+                while timers
+                    .peek()
+                    .is_some_and(|Reverse(e)| e.timer.is_cancelled())
+                {
+                    timers.pop();
+                }
+                if timers.len() > 2 * live.max(32) {
+                    timers.retain(|Reverse(e)| !e.timer.is_cancelled());
+                    live = timers.len();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // select 스레드를 멈추고 기다리는 태스크와 에러 핸들러에 에러를 통지한다.
+    fn fail(&self, err: io::Error) {
+        self.status.close();
+
+        let wakers = self
+            .wakers
+            .lock()
+            .unwrap()
+            .drain()
+            .flat_map(|(_, interest)| [interest.read, interest.write])
+            .flatten()
+            .collect::<Vec<_>>();
+        wakers.into_iter().for_each(|waker| waker.wake());
+
+        self.status.fail(err);
+    }
+
+    // 닫힌 후라면 op를 돌려준다.
+    fn push_op(&self, op: IOOps) -> Result<(), IOOps> {
+        let mut q = self.queue.lock().unwrap();
+        if self.status.is_closed() {
+            return Err(op);
+        }
+        q.push_back(op);
+        write_eventfd(self.event, 1);
+        Ok(())
+    }
+}
+
+impl Reactor for EpollReactor {
+    fn status(&self) -> &Status {
+        &self.status
+    }
+
+    fn register(&self, flags: EpollFlags, fd: RawFd, waker: Waker, mode: TriggerMode) {
+        let _ = self.push_op(IOOps::Add(flags, fd, waker, mode));
+    }
+
+    fn unregister(&self, fd: RawFd) {
+        let _ = self.push_op(IOOps::Remove(fd));
+    }
+
+    fn add_timer(&self, deadline: Instant, timer: Arc<Timer>) {
+        // 종료 후에는 기한을 확인하는 스레드가 없으므로 바로 깨운다.
+        if let Err(IOOps::Timer(_, timer)) = self.push_op(IOOps::Timer(deadline, timer)) {
+            timer.fire();
+        }
+    }
+
+    // select 스레드가 힙을 확인할 때 제거한다.
+    fn cancel_timer(&self, _timer: &Arc<Timer>) {}
+
+    fn take_fd_error(&self, fd: RawFd) -> Option<io::Error> {
+        self.wakers
+            .lock()
+            .unwrap()
+            .get_mut(&fd)
+            .and_then(|interest| interest.error.take())
+    }
+
+    fn shutdown(&self) {
+        if self.status.close() {
+            let mut q = self.queue.lock().unwrap();
+            q.push_back(IOOps::Shutdown);
+            write_eventfd(self.event, 1);
+        }
+    }
+
+    fn poll_recv(
+        &self,
+        fd: RawFd,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        poll_ready(self, libc::EPOLLIN, fd, cx, || socket::recv(fd, buf))
+    }
+
+    fn poll_send(&self, fd: RawFd, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        poll_ready(self, libc::EPOLLOUT, fd, cx, || socket::send(fd, buf))
+    }
+
+    // 버퍼링하지 않으므로 할 일이 없다.
+    fn poll_flush(&self, _fd: RawFd, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_accept(
+        &self,
+        fd: RawFd,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<(OwnedFd, SocketAddr)>> {
+        poll_ready(self, libc::EPOLLIN, fd, cx, || socket::accept(fd))
+    }
+}
+
+impl Drop for EpollReactor {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.epfd);
+            libc::close(self.event);
+        }
+    }
+}
@@ -0,0 +1,1028 @@
+use std::{
+    collections::HashMap,
+    ffi::c_void,
+    io, mem,
+    net::SocketAddr,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+    ptr,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+    time::Instant,
+};
+
+use super::{EpollFlags, Reactor, Status, TriggerMode};
+use crate::{socket::inet_addr, timer::Timer};
+
+// linux/io_uring.h의 정의
+// libc 크레이트에는 시스템 콜 번호만 있으므로 구조체와 상수는 직접 정의한다.
+const IORING_OFF_SQ_RING: libc::off_t = 0;
+const IORING_OFF_CQ_RING: libc::off_t = 0x8000000;
+const IORING_OFF_SQES: libc::off_t = 0x10000000;
+
+const IORING_SETUP_CQSIZE: u32 = 1 << 3;
+const IORING_FEAT_SINGLE_MMAP: u32 = 1 << 0;
+const IORING_ENTER_GETEVENTS: u32 = 1 << 0;
+
+const IORING_OP_POLL_ADD: u8 = 6;
+const IORING_OP_TIMEOUT: u8 = 11;
+const IORING_OP_ACCEPT: u8 = 13;
+const IORING_OP_ASYNC_CANCEL: u8 = 14;
+const IORING_OP_SEND: u8 = 26;
+const IORING_OP_RECV: u8 = 27;
+
+const IORING_POLL_ADD_MULTI: u32 = 1 << 0;
+const IORING_CQE_F_MORE: u32 = 1 << 1;
+const IORING_ASYNC_CANCEL_ALL: u32 = 1 << 0;
+const IORING_ASYNC_CANCEL_ANY: u32 = 1 << 2;
+
+const SQ_ENTRIES: u32 = 256;
+// 완료를 기다리는 IO가 많아도 넘치지 않도록 CQ는 크게 잡는다.
+const CQ_ENTRIES: u32 = 4096;
+
+// 한 번의 recv와 send로 다루는 최대 바이트 수
+const MAX_BUF: usize = 64 * 1024;
+
+#[repr(C)]
+#[derive(Default)]
+struct SqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    flags: u32,
+    dropped: u32,
+    array: u32,
+    resv1: u32,
+    user_addr: u64,
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct CqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    overflow: u32,
+    cqes: u32,
+    flags: u32,
+    resv1: u32,
+    user_addr: u64,
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct Params {
+    sq_entries: u32,
+    cq_entries: u32,
+    flags: u32,
+    sq_thread_cpu: u32,
+    sq_thread_idle: u32,
+    features: u32,
+    wq_fd: u32,
+    resv: [u32; 3],
+    sq_off: SqringOffsets,
+    cq_off: CqringOffsets,
+}
+
+// 공용체인 필드는 이 백엔드에서 사용하는 이름으로 정의한다.
+#[repr(C)]
+#[derive(Default)]
+struct Sqe {
+    opcode: u8,
+    flags: u8,
+    ioprio: u16,
+    fd: i32,
+    // off 또는 addr2
+    off: u64,
+    addr: u64,
+    len: u32,
+    // poll32_events, timeout_flags, accept_flags, cancel_flags, msg_flags
+    op_flags: u32,
+    user_data: u64,
+    buf_index: u16,
+    personality: u16,
+    splice_fd_in: i32,
+    addr3: u64,
+    pad: u64,
+}
+
+impl Sqe {
+    fn new(opcode: u8, fd: RawFd) -> Sqe {
+        Sqe {
+            opcode,
+            fd,
+            ..Default::default()
+        }
+    }
+}
+
+#[repr(C)]
+struct Cqe {
+    user_data: u64,
+    res: i32,
+    flags: u32,
+}
+
+#[repr(C)]
+struct Timespec {
+    tv_sec: i64,
+    tv_nsec: i64,
+}
+
+struct Mmap {
+    ptr: *mut c_void,
+    len: usize,
+}
+
+impl Mmap {
+    fn new(fd: &OwnedFd, len: usize, offset: libc::off_t) -> io::Result<Mmap> {
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_POPULATE,
+                fd.as_raw_fd(),
+                offset,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Mmap { ptr, len })
+    }
+
+    fn at<T>(&self, offset: u32) -> *mut T {
+        unsafe { self.ptr.add(offset as usize) as *mut T }
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.ptr, self.len);
+        }
+    }
+}
+
+// io_uring의 SQ와 CQ
+struct Ring {
+    sq_head: *const AtomicU32,
+    sq_tail: *const AtomicU32,
+    sq_mask: u32,
+    sq_entries: u32,
+    sq_array: *mut u32,
+    sqes: *mut Sqe,
+    cq_head: *const AtomicU32,
+    cq_tail: *const AtomicU32,
+    cq_mask: u32,
+    cqes: *const Cqe,
+    _sq_ring: Mmap,
+    _cq_ring: Option<Mmap>,
+    _sqe_ring: Mmap,
+    fd: OwnedFd,
+}
+
+// SQ에는 State의 락을 잡은 스레드만 쓰고 CQ는 리액터 스레드만 읽는다.
+unsafe impl Send for Ring {}
+unsafe impl Sync for Ring {}
+
+impl Ring {
+    fn new(entries: u32, cq_entries: u32) -> io::Result<Ring> {
+        let mut p = Params {
+            flags: IORING_SETUP_CQSIZE,
+            cq_entries,
+            ..Default::default()
+        };
+        let fd = unsafe { libc::syscall(libc::SYS_io_uring_setup, entries, &mut p as *mut Params) };
+        if fd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        let fd = unsafe { OwnedFd::from_raw_fd(fd as RawFd) };
+
+        let sq_len = p.sq_off.array as usize + p.sq_entries as usize * mem::size_of::<u32>();
+        let cq_len = p.cq_off.cqes as usize + p.cq_entries as usize * mem::size_of::<Cqe>();
+        // 오래된 커널이 아니라면 SQ와 CQ는 한 번의 mmap으로 매핑할 수 있다.
+        let single = p.features & IORING_FEAT_SINGLE_MMAP != 0;
+        let sq_ring = if single {
+            Mmap::new(&fd, sq_len.max(cq_len), IORING_OFF_SQ_RING)?
+        } else {
+            Mmap::new(&fd, sq_len, IORING_OFF_SQ_RING)?
+        };
+        let cq_ring = if single {
+            None
+        } else {
+            Some(Mmap::new(&fd, cq_len, IORING_OFF_CQ_RING)?)
+        };
+        let sqe_ring = Mmap::new(
+            &fd,
+            p.sq_entries as usize * mem::size_of::<Sqe>(),
+            IORING_OFF_SQES,
+        )?;
+
+        let cq = cq_ring.as_ref().unwrap_or(&sq_ring);
+        unsafe {
+            Ok(Ring {
+                sq_head: sq_ring.at(p.sq_off.head),
+                sq_tail: sq_ring.at(p.sq_off.tail),
+                sq_mask: *sq_ring.at::<u32>(p.sq_off.ring_mask),
+                sq_entries: p.sq_entries,
+                sq_array: sq_ring.at(p.sq_off.array),
+                sqes: sqe_ring.at(0),
+                cq_head: cq.at(p.cq_off.head),
+                cq_tail: cq.at(p.cq_off.tail),
+                cq_mask: *cq.at::<u32>(p.cq_off.ring_mask),
+                cqes: cq.at(p.cq_off.cqes),
+                _sq_ring: sq_ring,
+                _cq_ring: cq_ring,
+                _sqe_ring: sqe_ring,
+                fd,
+            })
+        }
+    }
+
+    // 커널이 아직 가져가지 않은 SQE의 수
+    fn sq_pending(&self) -> u32 {
+        unsafe {
+            let tail = (*self.sq_tail).load(Ordering::Relaxed);
+            let head = (*self.sq_head).load(Ordering::Acquire);
+            tail.wrapping_sub(head)
+        }
+    }
+
+    // SQ가 가득 찼다면 false
+    fn push(&self, sqe: Sqe) -> bool {
+        if self.sq_pending() == self.sq_entries {
+            return false;
+        }
+        unsafe {
+            let tail = (*self.sq_tail).load(Ordering::Relaxed);
+            let index = tail & self.sq_mask;
+            self.sqes.add(index as usize).write(sqe);
+            self.sq_array.add(index as usize).write(index);
+            (*self.sq_tail).store(tail.wrapping_add(1), Ordering::Release);
+        }
+        true
+    }
+
+    fn pop(&self) -> Option<Cqe> {
+        unsafe {
+            let head = (*self.cq_head).load(Ordering::Relaxed);
+            let tail = (*self.cq_tail).load(Ordering::Acquire);
+            if head == tail {
+                return None;
+            }
+            let cqe = self.cqes.add((head & self.cq_mask) as usize).read();
+            (*self.cq_head).store(head.wrapping_add(1), Ordering::Release);
+            Some(cqe)
+        }
+    }
+
+    fn enter(&self, to_submit: u32, min_complete: u32, flags: u32) -> io::Result<u32> {
+        let ret = unsafe {
+            libc::syscall(
+                libc::SYS_io_uring_enter,
+                self.fd.as_raw_fd(),
+                to_submit,
+                min_complete,
+                flags,
+                ptr::null::<libc::sigset_t>(),
+                0_usize,
+            )
+        };
+        if ret == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(ret as u32)
+        }
+    }
+}
+
+// 커널에 제출한 IO
+// 완료될 때까지 커널이 참조하는 버퍼를 보관한다.
+enum Op {
+    Poll {
+        fd: RawFd,
+    },
+    Recv {
+        fd: RawFd,
+        buf: Vec<u8>,
+    },
+    Send {
+        fd: RawFd,
+        buf: Vec<u8>,
+    },
+    Accept {
+        fd: RawFd,
+        addr: Box<(libc::sockaddr_storage, libc::socklen_t)>,
+    },
+    Timeout {
+        _ts: Box<Timespec>,
+        timer: Arc<Timer>,
+    },
+    Cancel,
+    // 종료 요청을 기다리는 eventfd의 POLL_ADD
+    Shutdown,
+}
+
+// 완료를 기다리는 IO의 상태
+#[derive(Default)]
+enum Pending<T> {
+    #[default]
+    Idle,
+    Busy(u64),
+    Done(T),
+}
+
+// fd별 상태
+// 완료된 IO의 id가 기록된 id와 다르면 이전의 fd에 대한 완료로 보고 무시한다.
+#[derive(Default)]
+struct FdState {
+    // 준비 상태를 기다리는 waker (POLL_ADD)
+    read: Option<Waker>,
+    write: Option<Waker>,
+    // 제출 중인 POLL_ADD
+    // 엣지 트리거에서는 read_poll 하나로 두 방향을 기다린다.
+    read_poll: Option<u64>,
+    write_poll: Option<u64>,
+    edge: bool,
+    read_ready: bool,
+    write_ready: bool,
+    error: Option<io::Error>,
+
+    // 수신한 데이터와 읽기 시작 위치
+    recv: Pending<io::Result<(Vec<u8>, usize)>>,
+    recv_waker: Option<Waker>,
+    send: Option<u64>,
+    send_error: Option<io::Error>,
+    send_waker: Option<Waker>,
+    accept: Pending<io::Result<(OwnedFd, SocketAddr)>>,
+    accept_waker: Option<Waker>,
+}
+
+impl FdState {
+    fn set_error(&mut self, err: io::Error) {
+        self.error = Some(err);
+        if let Some(waker) = self.read.take() {
+            waker.wake();
+        }
+        if let Some(waker) = self.write.take() {
+            waker.wake();
+        }
+    }
+
+    // 완료를 기다리는 IO
+    fn in_flight(&self) -> impl Iterator<Item = u64> {
+        let recv = match self.recv {
+            Pending::Busy(id) => Some(id),
+            _ => None,
+        };
+        let accept = match self.accept {
+            Pending::Busy(id) => Some(id),
+            _ => None,
+        };
+        [self.read_poll, self.write_poll, recv, accept]
+            .into_iter()
+            .flatten()
+    }
+
+    fn wakers(self) -> impl Iterator<Item = Waker> {
+        [
+            self.read,
+            self.write,
+            self.recv_waker,
+            self.send_waker,
+            self.accept_waker,
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+#[derive(Default)]
+struct Ops {
+    map: HashMap<u64, Op>,
+    next_id: u64,
+}
+
+#[derive(Default)]
+struct State {
+    ops: Ops,
+    fds: HashMap<RawFd, FdState>,
+    // Timer의 주소에서 제출한 TIMEOUT의 id
+    timers: HashMap<usize, u64>,
+}
+
+// io_uring으로 구현한 백엔드
+// 소켓 IO는 커널에 제출하고 완료되면 깨운다.
+// 준비 상태의 통지는 POLL_ADD, 타이머는 TIMEOUT으로 구현한다.
+pub(super) struct UringReactor {
+    ring: Ring,
+    state: Mutex<State>,
+    status: Status,
+    // 종료를 통지하는 eventfd
+    // SQ가 가득 차 있어도 리액터 스레드를 깨울 수 있도록 처음에 POLL_ADD를 제출해 둔다.
+    wake: OwnedFd,
+}
+
+impl UringReactor {
+    pub(super) fn new() -> io::Result<Arc<Self>> {
+        let wake = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) };
+        if wake < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let reactor = Arc::new(UringReactor {
+            ring: Ring::new(SQ_ENTRIES, CQ_ENTRIES)?,
+            state: Mutex::new(State::default()),
+            status: Status::new(),
+            wake: unsafe { OwnedFd::from_raw_fd(wake) },
+        });
+        {
+            let mut state = reactor.state.lock().unwrap();
+            let mut sqe = Sqe::new(IORING_OP_POLL_ADD, reactor.wake.as_raw_fd());
+            sqe.op_flags = libc::POLLIN as u32;
+            reactor.submit(&mut state.ops, sqe, Op::Shutdown)?;
+        }
+
+        let reactor_clone = reactor.clone();
+        std::thread::Builder::new()
+            .name("io-uring".to_string())
+            .spawn(move || reactor_clone.run())?;
+
+        Ok(reactor)
+    }
+
+    // SQE를 제출하고 완료 시에 참조할 id를 반환한다.
+    // State의 락을 잡은 상태에서 호출한다.
+    fn submit(&self, ops: &mut Ops, mut sqe: Sqe, op: Op) -> io::Result<u64> {
+        let id = ops.next_id;
+        ops.next_id += 1;
+        sqe.user_data = id;
+
+        if !self.ring.push(sqe) {
+            return Err(io::Error::from_raw_os_error(libc::EBUSY));
+        }
+        ops.map.insert(id, op);
+
+        // EAGAIN 등으로 제출하지 못한 SQE는 리액터 스레드가 다시 제출한다.
+        let _ = self.ring.enter(self.ring.sq_pending(), 0, 0);
+        Ok(id)
+    }
+
+    fn cancel(&self, ops: &mut Ops, target: u64) {
+        let mut sqe = Sqe::new(IORING_OP_ASYNC_CANCEL, -1);
+        sqe.addr = target;
+        let _ = self.submit(ops, sqe, Op::Cancel);
+    }
+
+    fn submit_poll(
+        &self,
+        ops: &mut Ops,
+        fd: RawFd,
+        events: u32,
+        multishot: bool,
+    ) -> io::Result<u64> {
+        let mut sqe = Sqe::new(IORING_OP_POLL_ADD, fd);
+        sqe.op_flags = events;
+        if multishot {
+            sqe.len = IORING_POLL_ADD_MULTI;
+        }
+        self.submit(ops, sqe, Op::Poll { fd })
+    }
+
+    fn run(&self) {
+        // 시그널은 signalfd로 받으므로 이 스레드에는 배송되지 않도록 한다.
+        unsafe {
+            let mut set: libc::sigset_t = mem::zeroed();
+            libc::sigfillset(&mut set);
+            libc::pthread_sigmask(libc::SIG_BLOCK, &set, ptr::null_mut());
+        }
+
+        if let Err(err) = self.run_loop() {
+            self.fail(err);
+        }
+
+        // 태스크를 참조하는 waker를 파기한다.
+        // 에러로 멈췄다면 완료되지 않은 타이머가 남으므로 깨운다.
+        let timers = {
+            let mut state = self.state.lock().unwrap();
+            state.fds.clear();
+            state
+                .ops
+                .map
+                .values()
+                .filter_map(|op| match op {
+                    Op::Timeout { timer, .. } => Some(timer.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        timers.into_iter().for_each(|timer| timer.fire());
+    }
+
+    fn run_loop(&self) -> io::Result<()> {
+        // 종료 중이라면 true
+        // 모든 IO가 완료되어 커널이 버퍼를 참조하지 않게 될 때까지 기다린다.
+        let mut draining = false;
+        // 남은 IO의 취소를 제출했다면 true
+        let mut cancelled = false;
+        loop {
+            let to_submit = self.ring.sq_pending();
+            // SQ가 가득 차서 취소를 제출하지 못했다면 기다리지 않고 다시 시도한다.
+            let min_complete = if draining && !cancelled { 0 } else { 1 };
+            if let Err(err) = self
+                .ring
+                .enter(to_submit, min_complete, IORING_ENTER_GETEVENTS)
+            {
+                match err.raw_os_error() {
+                    Some(libc::EINTR) | Some(libc::EAGAIN) | Some(libc::EBUSY) => (),
+                    _ => return Err(err),
+                }
+            }
+
+            let mut state = self.state.lock().unwrap();
+            while let Some(cqe) = self.ring.pop() {
+                if self.complete(&mut state, cqe) {
+                    draining = true;
+                }
+            }
+            if draining && !cancelled {
+                // 남은 IO를 모두 취소하고 완료될 때까지 기다린다.
+                let mut sqe = Sqe::new(IORING_OP_ASYNC_CANCEL, -1);
+                sqe.op_flags = IORING_ASYNC_CANCEL_ALL | IORING_ASYNC_CANCEL_ANY;
+                cancelled = self.submit(&mut state.ops, sqe, Op::Cancel).is_ok();
+            }
+            if draining && state.ops.map.is_empty() {
+                return Ok(());
+            }
+        }
+    }
+
+    // 완료한 IO의 결과를 fd의 상태에 반영하고 기다리는 태스크를 깨운다.
+    // 종료 요청이라면 true
+    fn complete(&self, state: &mut State, cqe: Cqe) -> bool {
+        let id = cqe.user_data;
+        let res = cqe.res;
+        let more = cqe.flags & IORING_CQE_F_MORE != 0;
+        let op = if more {
+            // 멀티샷은 마지막 완료까지 남겨 둔다.
+            match state.ops.map.get(&id) {
+                Some(Op::Poll { fd }) => Op::Poll { fd: *fd },
+                _ => return false,
+            }
+        } else {
+            match state.ops.map.remove(&id) {
+                Some(op) => op,
+                None => return false,
+            }
+        };
+
+        let result = if res < 0 {
+            Err(io::Error::from_raw_os_error(-res))
+        } else {
+            Ok(res as usize)
+        };
+
+        match op {
+            Op::Poll { fd } => {
+                let Some(f) = state.fds.get_mut(&fd) else {
+                    return false;
+                };
+                let read = f.read_poll == Some(id);
+                let write = f.write_poll == Some(id);
+                if !more && read {
+                    f.read_poll = None;
+                }
+                if !more && write {
+                    f.write_poll = None;
+                }
+                // 엣지 트리거는 read_poll 하나로 두 방향을 기다린다.
+                let (read, write) = if f.edge { (read, read) } else { (read, write) };
+
+                let revents = match result {
+                    Ok(revents) => revents as libc::c_short,
+                    Err(err) => {
+                        if (read || write) && res != -libc::ECANCELED {
+                            f.set_error(err);
+                        }
+                        return false;
+                    }
+                };
+                let hup = libc::POLLHUP | libc::POLLERR;
+                if read && revents & (libc::POLLIN | libc::POLLRDHUP | hup) != 0 {
+                    match f.read.take() {
+                        Some(waker) => waker.wake(),
+                        None => f.read_ready = f.edge,
+                    }
+                }
+                if write && revents & (libc::POLLOUT | hup) != 0 {
+                    match f.write.take() {
+                        Some(waker) => waker.wake(),
+                        None => f.write_ready = f.edge,
+                    }
+                }
+            }
+            Op::Recv { fd, mut buf } => {
+                let Some(f) = state.fds.get_mut(&fd) else {
+                    return false;
+                };
+                if !matches!(f.recv, Pending::Busy(busy) if busy == id) {
+                    return false;
+                }
+                f.recv = Pending::Done(result.map(|n| {
+                    buf.truncate(n);
+                    (buf, 0)
+                }));
+                if let Some(waker) = f.recv_waker.take() {
+                    waker.wake();
+                }
+            }
+            Op::Send { fd, mut buf } => {
+                let Some(f) = state.fds.get_mut(&fd) else {
+                    return false;
+                };
+                if f.send != Some(id) {
+                    return false;
+                }
+                f.send = None;
+                match result {
+                    // 일부만 보냈다면 남은 데이터를 다시 제출한다.
+                    Ok(n) if n < buf.len() && !self.status.is_closed() => {
+                        buf.drain(..n);
+                        let mut sqe = Sqe::new(IORING_OP_SEND, fd);
+                        sqe.addr = buf.as_ptr() as u64;
+                        sqe.len = buf.len() as u32;
+                        sqe.op_flags = libc::MSG_NOSIGNAL as u32;
+                        match self.submit(&mut state.ops, sqe, Op::Send { fd, buf }) {
+                            Ok(id) => {
+                                f.send = Some(id);
+                                return false;
+                            }
+                            Err(err) => f.send_error = Some(err),
+                        }
+                    }
+                    Ok(_) => (),
+                    Err(err) => f.send_error = Some(err),
+                }
+                if let Some(waker) = f.send_waker.take() {
+                    waker.wake();
+                }
+            }
+            Op::Accept { fd, addr } => {
+                // 받아들인 소켓은 전달할 곳이 없으면 여기서 닫힌다.
+                let accepted = result.and_then(|stream| {
+                    let stream = unsafe { OwnedFd::from_raw_fd(stream as RawFd) };
+                    Ok((stream, inet_addr(&addr.0)?))
+                });
+                let Some(f) = state.fds.get_mut(&fd) else {
+                    return false;
+                };
+                if !matches!(f.accept, Pending::Busy(busy) if busy == id) {
+                    return false;
+                }
+                f.accept = Pending::Done(accepted);
+                if let Some(waker) = f.accept_waker.take() {
+                    waker.wake();
+                }
+            }
+            Op::Timeout { timer, .. } => {
+                state.timers.remove(&(Arc::as_ptr(&timer) as usize));
+                // 종료할 때 취소된 타이머도 Sleep이 종료를 확인할 수 있도록 깨운다.
+                if res != -libc::ECANCELED || self.status.is_closed() {
+                    timer.fire();
+                }
+            }
+            Op::Cancel => (),
+            Op::Shutdown => return true,
+        }
+
+        false
+    }
+
+    // 리액터 스레드를 멈추고 기다리는 태스크와 에러 핸들러에 에러를 통지한다.
+    fn fail(&self, err: io::Error) {
+        self.status.close();
+
+        let wakers = self
+            .state
+            .lock()
+            .unwrap()
+            .fds
+            .drain()
+            .flat_map(|(_, f)| f.wakers())
+            .collect::<Vec<_>>();
+        wakers.into_iter().for_each(|waker| waker.wake());
+
+        self.status.fail(err);
+    }
+}
+
+impl Reactor for UringReactor {
+    fn status(&self) -> &Status {
+        &self.status
+    }
+
+    fn register(&self, flags: EpollFlags, fd: RawFd, waker: Waker, mode: TriggerMode) {
+        let mut state = self.state.lock().unwrap();
+        if self.status.is_closed() {
+            return;
+        }
+        let state = &mut *state;
+        let f = state.fds.entry(fd).or_default();
+
+        let edge = mode == TriggerMode::Edge;
+        if f.edge != edge {
+            for id in [f.read_poll.take(), f.write_poll.take()]
+                .into_iter()
+                .flatten()
+            {
+                self.cancel(&mut state.ops, id);
+            }
+            f.edge = edge;
+        }
+
+        if flags & libc::EPOLLIN != 0 {
+            if edge && f.read_ready {
+                f.read_ready = false;
+                waker.wake_by_ref();
+            } else {
+                f.read = Some(waker.clone());
+            }
+        }
+        if flags & libc::EPOLLOUT != 0 {
+            if edge && f.write_ready {
+                f.write_ready = false;
+                waker.wake_by_ref();
+            } else {
+                f.write = Some(waker);
+            }
+        }
+
+        let read = (libc::POLLIN | libc::POLLRDHUP) as u32;
+        let write = libc::POLLOUT as u32;
+        let result = if edge {
+            match f.read_poll {
+                Some(_) => Ok(()),
+                None => self
+                    .submit_poll(&mut state.ops, fd, read | write, true)
+                    .map(|id| f.read_poll = Some(id)),
+            }
+        } else {
+            let mut result = Ok(());
+            if f.read.is_some() && f.read_poll.is_none() {
+                result = self
+                    .submit_poll(&mut state.ops, fd, read, false)
+                    .map(|id| f.read_poll = Some(id));
+            }
+            if result.is_ok() && f.write.is_some() && f.write_poll.is_none() {
+                result = self
+                    .submit_poll(&mut state.ops, fd, write, false)
+                    .map(|id| f.write_poll = Some(id));
+            }
+            result
+        };
+        if let Err(err) = result {
+            f.set_error(err);
+        }
+    }
+
+    // 수신과 accept는 취소하고 송신은 완료될 때까지 계속한다.
+    // flush하지 않고 파기한 송신이 일부만 완료되었다면 남은 데이터는 버려진다.
+    fn unregister(&self, fd: RawFd) {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        let Some(f) = state.fds.remove(&fd) else {
+            return;
+        };
+        if self.status.is_closed() {
+            return;
+        }
+        for id in f.in_flight() {
+            self.cancel(&mut state.ops, id);
+        }
+    }
+
+    fn add_timer(&self, deadline: Instant, timer: Arc<Timer>) {
+        let mut state = self.state.lock().unwrap();
+        // 종료 후에는 완료를 처리하는 스레드가 없으므로 바로 깨운다.
+        if self.status.is_closed() {
+            drop(state);
+            timer.fire();
+            return;
+        }
+
+        let dur = deadline.saturating_duration_since(Instant::now());
+        let ts = Box::new(Timespec {
+            tv_sec: dur.as_secs() as i64,
+            tv_nsec: dur.subsec_nanos() as i64,
+        });
+        let mut sqe = Sqe::new(IORING_OP_TIMEOUT, -1);
+        sqe.addr = &*ts as *const Timespec as u64;
+        sqe.len = 1;
+        let op = Op::Timeout {
+            _ts: ts,
+            timer: timer.clone(),
+        };
+        // 제출하지 못했다면 바로 깨운다. Sleep은 기한 전이라면 다시 등록한다.
+        match self.submit(&mut state.ops, sqe, op) {
+            Ok(id) => {
+                state.timers.insert(Arc::as_ptr(&timer) as usize, id);
+            }
+            Err(_) => {
+                drop(state);
+                timer.fire();
+            }
+        }
+    }
+
+    // 커널의 TIMEOUT을 취소한다. 완료는 ECANCELED가 되므로 깨우지 않는다.
+    fn cancel_timer(&self, timer: &Arc<Timer>) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(id) = state.timers.remove(&(Arc::as_ptr(timer) as usize)) {
+            self.cancel(&mut state.ops, id);
+        }
+    }
+
+    fn take_fd_error(&self, fd: RawFd) -> Option<io::Error> {
+        self.state
+            .lock()
+            .unwrap()
+            .fds
+            .get_mut(&fd)
+            .and_then(|f| f.error.take())
+    }
+
+    fn shutdown(&self) {
+        // 등록과 타이머의 추가는 락을 잡고 종료 여부를 확인하므로 락을 잡은 상태에서 닫는다.
+        let _state = self.state.lock().unwrap();
+        if self.status.close() {
+            // SQE를 제출하지 않으므로 SQ가 가득 차 있어도 리액터 스레드를 깨울 수 있다.
+            let n = 1_u64;
+            unsafe {
+                libc::write(
+                    self.wake.as_raw_fd(),
+                    &n as *const u64 as *const c_void,
+                    mem::size_of::<u64>(),
+                )
+            };
+        }
+    }
+
+    fn poll_recv(
+        &self,
+        fd: RawFd,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(err) = self.status.error() {
+            return Poll::Ready(Err(err));
+        }
+        let state = &mut *state;
+        let f = state.fds.entry(fd).or_default();
+
+        match mem::take(&mut f.recv) {
+            // 다 읽지 못한 데이터는 다음 호출까지 남겨 둔다.
+            Pending::Done(Ok((data, pos))) => {
+                let n = buf.len().min(data.len() - pos);
+                buf[..n].copy_from_slice(&data[pos..pos + n]);
+                if pos + n < data.len() {
+                    f.recv = Pending::Done(Ok((data, pos + n)));
+                }
+                Poll::Ready(Ok(n))
+            }
+            Pending::Done(Err(err)) => Poll::Ready(Err(err)),
+            Pending::Busy(id) => {
+                f.recv = Pending::Busy(id);
+                f.recv_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+            Pending::Idle => {
+                if buf.is_empty() {
+                    return Poll::Ready(Ok(0));
+                }
+                let mut data = vec![0; buf.len().min(MAX_BUF)];
+                let mut sqe = Sqe::new(IORING_OP_RECV, fd);
+                sqe.addr = data.as_mut_ptr() as u64;
+                sqe.len = data.len() as u32;
+                match self.submit(&mut state.ops, sqe, Op::Recv { fd, buf: data }) {
+                    Ok(id) => {
+                        f.recv = Pending::Busy(id);
+                        f.recv_waker = Some(cx.waker().clone());
+                        Poll::Pending
+                    }
+                    Err(err) => Poll::Ready(Err(err)),
+                }
+            }
+        }
+    }
+
+    // 데이터를 복사해서 제출하면 바로 완료로 한다.
+    // 이전 송신이 완료될 때까지는 다음 데이터를 받지 않는다.
+    fn poll_send(&self, fd: RawFd, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(err) = self.status.error() {
+            return Poll::Ready(Err(err));
+        }
+        let state = &mut *state;
+        let f = state.fds.entry(fd).or_default();
+
+        if let Some(err) = f.send_error.take() {
+            return Poll::Ready(Err(err));
+        }
+        if f.send.is_some() {
+            f.send_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let data = buf[..buf.len().min(MAX_BUF)].to_vec();
+        let n = data.len();
+        let mut sqe = Sqe::new(IORING_OP_SEND, fd);
+        sqe.addr = data.as_ptr() as u64;
+        sqe.len = n as u32;
+        sqe.op_flags = libc::MSG_NOSIGNAL as u32;
+        match self.submit(&mut state.ops, sqe, Op::Send { fd, buf: data }) {
+            Ok(id) => {
+                f.send = Some(id);
+                Poll::Ready(Ok(n))
+            }
+            Err(err) => Poll::Ready(Err(err)),
+        }
+    }
+
+    fn poll_flush(&self, fd: RawFd, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(err) = self.status.error() {
+            return Poll::Ready(Err(err));
+        }
+        let Some(f) = state.fds.get_mut(&fd) else {
+            return Poll::Ready(Ok(()));
+        };
+
+        if let Some(err) = f.send_error.take() {
+            return Poll::Ready(Err(err));
+        }
+        if f.send.is_some() {
+            f.send_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_accept(
+        &self,
+        fd: RawFd,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<(OwnedFd, SocketAddr)>> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(err) = self.status.error() {
+            return Poll::Ready(Err(err));
+        }
+        let state = &mut *state;
+        let f = state.fds.entry(fd).or_default();
+
+        match mem::take(&mut f.accept) {
+            Pending::Done(result) => Poll::Ready(result),
+            Pending::Busy(id) => {
+                f.accept = Pending::Busy(id);
+                f.accept_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+            Pending::Idle => {
+                let mut addr = Box::new((
+                    unsafe { mem::zeroed::<libc::sockaddr_storage>() },
+                    mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t,
+                ));
+                let mut sqe = Sqe::new(IORING_OP_ACCEPT, fd);
+                sqe.addr = &mut addr.0 as *mut libc::sockaddr_storage as u64;
+                sqe.off = &mut addr.1 as *mut libc::socklen_t as u64;
+                sqe.op_flags = (libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC) as u32;
+                match self.submit(&mut state.ops, sqe, Op::Accept { fd, addr }) {
+                    Ok(id) => {
+                        f.accept = Pending::Busy(id);
+                        f.accept_waker = Some(cx.waker().clone());
+                        Poll::Pending
+                    }
+                    Err(err) => Poll::Ready(Err(err)),
+                }
+            }
+        }
+    }
+}
+
+impl Drop for UringReactor {
+    fn drop(&mut self) {
+        // 리액터 스레드가 에러로 멈췄다면 커널이 아직 버퍼를 참조하고 있을 수 있으므로 해제하지 않는다.
+        let ops = mem::take(&mut self.state.get_mut().unwrap().ops.map);
+        if !ops.is_empty() {
+            mem::forget(ops);
+        }
+    }
+}
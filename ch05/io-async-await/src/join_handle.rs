@@ -0,0 +1,193 @@
+use std::{
+    any::Any,
+    fmt,
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+};
+
+pub enum JoinError {
+    // abort 또는 Executor 종료로 취소됨
+    Cancelled,
+    // 태스크가 패닉을 일으킴
+    Panic(Box<dyn Any + Send + 'static>),
+}
+
+impl JoinError {
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, JoinError::Cancelled)
+    }
+
+    pub fn is_panic(&self) -> bool {
+        matches!(self, JoinError::Panic(_))
+    }
+
+    pub fn into_panic(self) -> Box<dyn Any + Send + 'static> {
+        match self {
+            JoinError::Panic(p) => p,
+            JoinError::Cancelled => panic!("task was cancelled, not panicked"),
+        }
+    }
+}
+
+impl fmt::Debug for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JoinError::Cancelled => write!(f, "JoinError::Cancelled"),
+            JoinError::Panic(_) => write!(f, "JoinError::Panic(..)"),
+        }
+    }
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JoinError::Cancelled => write!(f, "task was cancelled"),
+            JoinError::Panic(p) => {
+                // panic!에 전달된 메시지는 &str 또는 String
+                let msg = p
+                    .downcast_ref::<&str>()
+                    .copied()
+                    .or_else(|| p.downcast_ref::<String>().map(|s| s.as_str()));
+                match msg {
+                    Some(msg) => write!(f, "task panicked: {}", msg),
+                    None => write!(f, "task panicked"),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for JoinError {}
+
+struct JoinState<T> {
+    result: Option<Result<T, JoinError>>,
+    // 결과를 기다리는 JoinHandle의 waker
+    waker: Option<Waker>,
+    finished: bool,
+}
+
+// 태스크와 JoinHandle이 공유하는 상태
+struct JoinInner<T> {
+    state: Mutex<JoinState<T>>,
+    aborted: AtomicBool,
+}
+
+impl<T> JoinInner<T> {
+    fn complete(&self, result: Result<T, JoinError>) {
+        let waker = {
+            let mut state = self.state.lock().unwrap();
+            if state.finished {
+                return;
+            }
+            state.finished = true;
+            state.result = Some(result);
+            state.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+// 사용자의 Future를 감싸서 결과와 패닉을 JoinHandle에 전달하는 Future
+pub(crate) struct TaskFuture<F: Future> {
+    future: Option<Pin<Box<F>>>,
+    inner: Arc<JoinInner<F::Output>>,
+}
+
+impl<F: Future> Future for TaskFuture<F> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.inner.aborted.load(Ordering::Acquire) && self.future.take().is_some() {
+            self.inner.complete(Err(JoinError::Cancelled));
+        }
+
+        let Some(future) = self.future.as_mut() else {
+            return Poll::Ready(());
+        };
+
+        let result = match panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(cx))) {
+            Ok(Poll::Pending) => return Poll::Pending,
+            Ok(Poll::Ready(v)) => Ok(v),
+            Err(p) => Err(JoinError::Panic(p)),
+        };
+
+        self.future = None;
+        self.inner.complete(result);
+        Poll::Ready(())
+    }
+}
+
+impl<F: Future> Drop for TaskFuture<F> {
+    fn drop(&mut self) {
+        // 완료 전에 파기되었다면 취소로 취급
+        if self.future.take().is_some() {
+            self.inner.complete(Err(JoinError::Cancelled));
+        }
+    }
+}
+
+pub struct JoinHandle<T> {
+    inner: Arc<JoinInner<T>>,
+    // abort 시에 태스크를 다시 스케줄링하기 위한 waker
+    task: Waker,
+}
+
+impl<T> JoinHandle<T> {
+    // 태스크를 취소한다.
+    // 다음 번에 스케줄링될 때 Future가 파기되고 JoinError::Cancelled가 반환된다.
+    pub fn abort(&self) {
+        self.inner.aborted.store(true, Ordering::Release);
+        self.task.wake_by_ref();
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.inner.state.lock().unwrap().finished
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.inner.state.lock().unwrap();
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None if state.finished => panic!("JoinHandle polled after completion"),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+// future를 TaskFuture로 감싼다.
+// 반환된 클로저에 태스크의 waker를 전달하면 JoinHandle을 얻을 수 있다.
+pub(crate) fn task_future<F: Future>(
+    future: F,
+) -> (TaskFuture<F>, impl FnOnce(Waker) -> JoinHandle<F::Output>) {
+    let inner = Arc::new(JoinInner {
+        state: Mutex::new(JoinState {
+            result: None,
+            waker: None,
+            finished: false,
+        }),
+        aborted: AtomicBool::new(false),
+    });
+
+    let task_future = TaskFuture {
+        future: Some(Box::pin(future)),
+        inner: inner.clone(),
+    };
+
+    (task_future, move |task| JoinHandle { inner, task })
+}
@@ -1,4 +1,16 @@
+pub mod async_io;
 pub mod async_listener;
 pub mod async_reader;
+pub mod async_tcp_stream;
+pub mod async_udp_socket;
+pub mod async_unix;
+pub mod blocking;
+pub mod combinator;
 pub mod excutor;
+pub mod fs;
 pub mod io_selector;
+pub mod join_handle;
+pub mod signal;
+mod socket;
+pub mod sync;
+pub mod timer;
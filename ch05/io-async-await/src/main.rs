@@ -1,29 +1,374 @@
-use std::io::Write;
+use std::{
+    future::{self, Future},
+    io::{self, Write},
+    net::{TcpListener, TcpStream},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Waker},
+    thread,
+    time::{Duration, Instant},
+};
 
-use io_async_await::{async_listener::AsyncListener, excutor::Executor, io_selector::IOSelector};
+use clap::{Arg, ArgAction, Command};
+use io_async_await::{
+    async_io::{AsyncRead, AsyncWrite},
+    async_listener::AsyncListener,
+    async_reader::AsyncReader,
+    combinator::{join, join_all, select, try_join, try_join_all, Either},
+    excutor::Executor,
+    io_selector::{Backend, IOSelector},
+    join_handle::JoinHandle,
+    signal::signal,
+    sync::{mpsc, CancellationToken},
+    timer::{interval, sleep, timeout},
+};
 
-fn main() {
+struct CommandArgs {
+    backend: Backend,
+    check: bool,
+}
+
+fn get_command_args() -> CommandArgs {
+    let matches = Command::new("io-async-await")
+        .arg(
+            Arg::new("backend")
+                .short('b')
+                .long("backend")
+                .value_name("BACKEND")
+                .num_args(1)
+                .help("IOSelector backend (epoll or io_uring)")
+                .value_parser(|s: &str| s.parse::<Backend>())
+                .default_value("epoll"),
+        )
+        .arg(
+            Arg::new("check")
+                .long("check")
+                .help("Run self checks and exit")
+                .action(ArgAction::SetTrue),
+        )
+        .get_matches();
+
+    CommandArgs {
+        backend: matches.get_one("backend").cloned().unwrap(),
+        check: matches.get_flag("check"),
+    }
+}
+
+// 빈 자리를 기다리는 send를 수신 측을 닫은 후에 완료하거나 파기한다.
+fn check_mpsc() {
+    let mut cx = Context::from_waker(Waker::noop());
+
+    // 기다리는 중에 닫히면 대기열에서 나온다.
+    let (tx, mut rx) = mpsc::channel(1);
+    tx.try_send(0).unwrap();
+    let mut send = tx.send(1);
+    assert!(Pin::new(&mut send).poll(&mut cx).is_pending());
+    let mut send2 = tx.send(2);
+    assert!(Pin::new(&mut send2).poll(&mut cx).is_pending());
+    rx.close();
+    drop(send);
+    assert!(matches!(
+        Pin::new(&mut send2).poll(&mut cx),
+        Poll::Ready(Err(_))
+    ));
+    assert_eq!(rx.try_recv(), Ok(0));
+
+    // 자리가 예약된 send를 파기하면 다음 send가 그 자리를 사용한다.
+    let (tx, mut rx) = mpsc::channel(1);
+    tx.try_send(0).unwrap();
+    let mut send = tx.send(1);
+    assert!(Pin::new(&mut send).poll(&mut cx).is_pending());
+    assert_eq!(rx.try_recv(), Ok(0));
+    drop(send);
+    tx.try_send(2).unwrap();
+
+    // 자리가 예약된 후에 닫히면 에러가 되고 자리를 되돌린다.
+    let mut send = tx.send(3);
+    assert!(Pin::new(&mut send).poll(&mut cx).is_pending());
+    assert_eq!(rx.try_recv(), Ok(2));
+    rx.close();
+    assert!(matches!(
+        Pin::new(&mut send).poll(&mut cx),
+        Poll::Ready(Err(_))
+    ));
+    drop(send);
+    assert!(rx.try_recv().is_err());
+    println!("mpsc: ok");
+}
+
+// 파기되면 flag를 설정한다.
+struct DropFlag(Arc<AtomicBool>);
+
+impl Drop for DropFlag {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+// select는 진 쪽을 완료 시점에 파기하고, try_join은 Err를 받으면 다른 쪽을 기다리지 않는다.
+fn check_combinator() {
+    let mut cx = Context::from_waker(Waker::noop());
+
+    let dropped = Arc::new(AtomicBool::new(false));
+    let flag = DropFlag(dropped.clone());
+    let loser = async move {
+        let _flag = flag;
+        future::pending::<()>().await
+    };
+    let mut s = select(async { 1 }, loser);
+    assert!(matches!(
+        Pin::new(&mut s).poll(&mut cx),
+        Poll::Ready(Either::Left(1))
+    ));
+    assert!(dropped.load(Ordering::SeqCst));
+
+    let mut s = select(future::pending::<()>(), async { 2 });
+    assert!(matches!(
+        Pin::new(&mut s).poll(&mut cx),
+        Poll::Ready(Either::Right(2))
+    ));
+
+    // 어느 쪽이 먼저 Err가 되어도 완료되지 않은 쪽을 기다리지 않는다.
+    let mut j = try_join(
+        async { Err::<(), _>(1) },
+        future::pending::<Result<(), i32>>(),
+    );
+    assert!(matches!(
+        Pin::new(&mut j).poll(&mut cx),
+        Poll::Ready(Err(1))
+    ));
+    let mut j = try_join(future::pending::<Result<(), i32>>(), async {
+        Err::<(), _>(2)
+    });
+    assert!(matches!(
+        Pin::new(&mut j).poll(&mut cx),
+        Poll::Ready(Err(2))
+    ));
+    let mut j = try_join(async { Ok::<_, i32>(1) }, async { Ok(2) });
+    assert!(matches!(
+        Pin::new(&mut j).poll(&mut cx),
+        Poll::Ready(Ok((1, 2)))
+    ));
+
+    let mut j = try_join_all((0..3).map(|i| async move {
+        match i {
+            0 => future::pending().await,
+            1 => Err(3),
+            _ => Ok(4),
+        }
+    }));
+    assert!(matches!(
+        Pin::new(&mut j).poll(&mut cx),
+        Poll::Ready(Err(3))
+    ));
+    println!("combinator: ok");
+}
+
+// 다른 스레드의 취소로 기다리던 태스크가 깨어나고 자식 토큰에 전파된다.
+fn check_cancellation_token() {
     let executor = Executor::new();
-    let selector = IOSelector::new();
+    let token = CancellationToken::new();
+    let child = token.child_token();
+    let grandchild = child.child_token();
+
+    // 자식을 취소해도 부모는 취소되지 않는다.
+    let other = token.child_token();
+    other.cancel();
+    assert!(other.is_cancelled());
+    assert!(!token.is_cancelled());
+
+    let t = token.clone();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        t.cancel();
+    });
+    executor.block_on(async {
+        join(token.cancelled(), grandchild.cancelled()).await;
+    });
+    assert!(token.is_cancelled());
+    assert!(child.is_cancelled());
+    assert!(grandchild.is_cancelled());
+
+    // 취소된 후에 만든 토큰과 Future는 바로 완료된다.
+    let late = token.child_token();
+    assert!(late.is_cancelled());
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut cancelled = late.cancelled();
+    assert!(Pin::new(&mut cancelled).poll(&mut cx).is_ready());
+    println!("cancellation token: ok");
+}
+
+// 여러 번에 나뉘어 도착한 줄과 EOF 전의 줄바꿈 없는 줄을 읽는다.
+fn check_read_line(backend: Backend) -> io::Result<()> {
+    let executor = Executor::new();
+    let selector = IOSelector::with_backend(backend)?;
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let mut writer = TcpStream::connect(listener.local_addr()?)?;
+    let (stream, _) = listener.accept()?;
+
+    let sender = thread::spawn(move || -> io::Result<()> {
+        writer.write_all(b"hello\nwor")?;
+        thread::sleep(Duration::from_millis(20));
+        writer.write_all("ld\n\n한글\nlast".as_bytes())?;
+        Ok(())
+    });
+
+    let mut reader = AsyncReader::new(stream, selector)?;
+    let lines = executor.block_on(async {
+        let mut lines = Vec::new();
+        while let Some(line) = reader.read_line().await? {
+            lines.push(line);
+        }
+        // EOF 후에도 None을 반환한다.
+        assert!(reader.read_line().await?.is_none());
+        io::Result::Ok(lines)
+    })?;
+    sender.join().unwrap()?;
+    assert_eq!(lines, ["hello\n", "world\n", "\n", "한글\n", "last"]);
+    println!("read_line: ok");
+    Ok(())
+}
+
+// 타이머는 기한까지 기다리고, IOSelector가 종료하면 기다리던 타이머가 완료된다.
+fn check_timer(backend: Backend) -> io::Result<()> {
+    let executor = Executor::new();
+    let selector = IOSelector::with_backend(backend)?;
+    let hour = Duration::from_secs(3600);
+    let ms = Duration::from_millis;
+
+    executor.block_on(async {
+        let start = Instant::now();
+        sleep(ms(30), selector.clone()).await;
+        assert!(start.elapsed() >= ms(30));
+
+        // 먼저 기한이 오는 쪽이 완료되고 진 쪽의 타이머는 취소된다.
+        let start = Instant::now();
+        let first = select(
+            sleep(hour, selector.clone()),
+            sleep(ms(10), selector.clone()),
+        )
+        .await;
+        assert!(matches!(first, Either::Right(())));
+        assert!(start.elapsed() < hour);
+
+        let result = timeout(ms(10), future::pending::<()>(), selector.clone()).await;
+        assert!(result.is_err());
+        let result = timeout(hour, async { 5 }, selector.clone()).await;
+        assert_eq!(result.ok(), Some(5));
+
+        // 기한을 앞당기면 새 기한에 완료된다.
+        let mut s = sleep(hour, selector.clone());
+        let mut s = Pin::new(&mut s);
+        assert!(future::poll_fn(|cx| Poll::Ready(s.as_mut().poll(cx).is_pending())).await);
+        s.reset(Instant::now() + ms(10));
+        s.await;
+
+        let start = Instant::now();
+        let mut interval = interval(ms(10), selector.clone());
+        for _ in 0..3 {
+            interval.tick().await;
+        }
+        assert!(start.elapsed() >= ms(20));
+    });
+
+    let mut interval = interval(hour, selector.clone());
+
+    let s = selector.clone();
+    let (_, result) = executor.block_on(async {
+        // 처음의 틱은 바로 완료된다.
+        interval.tick().await;
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            s.shutdown();
+        });
+        join(
+            join(sleep(hour, selector.clone()), interval.tick()),
+            timeout(hour, future::pending::<()>(), selector.clone()),
+        )
+        .await
+    });
+    assert!(result.is_err());
+    println!("timer: ok");
+    Ok(())
+}
+
+fn main() -> io::Result<()> {
+    let args = get_command_args();
+    if args.check {
+        check_mpsc();
+        check_combinator();
+        check_cancellation_token();
+        check_read_line(args.backend)?;
+        return check_timer(args.backend);
+    }
+
+    let executor = Executor::new();
+    let selector = IOSelector::with_backend(args.backend)?;
+    println!("backend: {}", selector.backend());
     let spawner = executor.get_spawner();
+    executor.attach_selector(selector.clone());
+
+    // 워커 스레드를 생성하기 전에 SIGINT를 블록한다.
+    let mut sigint = signal(libc::SIGINT, selector.clone())?;
+    let token = CancellationToken::new();
 
+    let listener = AsyncListener::listen("127.0.0.1:10000", selector.clone())?;
+    let shutdown = executor.shutdown_handle();
+    let server_token = token.clone();
     let server = async move {
-        let listener = AsyncListener::listen("127.0.0.1:10000", selector.clone());
+        let mut handlers: Vec<JoinHandle<()>> = Vec::new();
         loop {
-            let (mut reader, mut writer, addr) = listener.accept().await;
+            let accepted = match select(listener.accept(), server_token.cancelled()).await {
+                Either::Left(accepted) => accepted,
+                Either::Right(()) => break,
+            };
+            let (mut stream, addr) = match accepted {
+                Ok(accepted) => accepted,
+                Err(err) => {
+                    eprintln!("accept error: {}", err);
+                    break;
+                }
+            };
             println!("accept: {}", addr);
 
-            spawner.spawn(async move {
-                while let Some(buf) = reader.read_line().await {
-                    print!("read: {}, {}", addr, buf);
-                    writer.write_all(buf.as_bytes()).unwrap();
-                    writer.flush().unwrap();
+            let token = server_token.clone();
+            handlers.retain(|h| !h.is_finished());
+            handlers.push(spawner.spawn(async move {
+                let mut buf = [0; 1024];
+                loop {
+                    let n = match select(stream.read(&mut buf), token.cancelled()).await {
+                        Either::Left(Ok(0)) | Either::Right(()) => break,
+                        Either::Left(Ok(n)) => n,
+                        Either::Left(Err(err)) => {
+                            eprintln!("read error: {}, {}", addr, err);
+                            break;
+                        }
+                    };
+                    print!("read: {}, {}", addr, String::from_utf8_lossy(&buf[..n]));
+                    if let Err(err) = stream.write_all(&buf[..n]).await {
+                        eprintln!("write error: {}, {}", addr, err);
+                        break;
+                    }
                 }
                 println!("close: {}", addr);
-            });
+            }));
         }
+
+        // 처리 중인 커넥션이 닫힐 때까지 기다린 후에 종료한다.
+        join_all(handlers).await;
+        shutdown.shutdown();
     };
 
+    executor.get_spawner().spawn(async move {
+        match sigint.recv().await {
+            Ok(()) => println!("received SIGINT, shutting down"),
+            Err(err) => eprintln!("signal error: {}", err),
+        }
+        token.cancel();
+    });
     executor.get_spawner().spawn(server);
-    executor.run();
+    executor.run()
 }
@@ -0,0 +1,98 @@
+use std::{
+    ffi::c_int,
+    future::Future,
+    io, mem,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use crate::{async_io::poll_io, io_selector::IOSelector};
+
+// signalfd로 시그널을 받는 스트림
+// 같은 시그널에 대해 여러 개를 만들면 시그널은 그중 하나에만 전달된다.
+pub struct Signal {
+    fd: OwnedFd,
+    signum: c_int,
+    selector: Arc<IOSelector>,
+}
+
+// signum을 블록하고 signalfd로 받을 수 있도록 한다.
+// 시그널 마스크는 스레드를 생성할 때 상속되므로 Executor::run을 호출하기 전에 메인 스레드에서 호출한다.
+// 블록한 시그널은 Signal을 파기해도 원래대로 돌아가지 않는다.
+pub fn signal(signum: c_int, selector: Arc<IOSelector>) -> io::Result<Signal> {
+    unsafe {
+        let mut set: libc::sigset_t = mem::zeroed();
+        libc::sigemptyset(&mut set);
+        if libc::sigaddset(&mut set, signum) == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let ret = libc::pthread_sigmask(libc::SIG_BLOCK, &set, std::ptr::null_mut());
+        if ret != 0 {
+            return Err(io::Error::from_raw_os_error(ret));
+        }
+
+        let fd = libc::signalfd(-1, &set, libc::SFD_NONBLOCK | libc::SFD_CLOEXEC);
+        if fd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Signal {
+            fd: OwnedFd::from_raw_fd(fd),
+            signum,
+            selector,
+        })
+    }
+}
+
+impl Signal {
+    pub fn signum(&self) -> c_int {
+        self.signum
+    }
+
+    // 시그널을 받을 때까지 기다린다.
+    // 기다리는 동안 여러 번 도착한 시그널은 한 번으로 합쳐질 수 있다.
+    pub fn recv(&mut self) -> Recv<'_> {
+        Recv { signal: self }
+    }
+
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let fd = self.fd.as_raw_fd();
+        poll_io(&self.selector, libc::EPOLLIN, fd, cx, || unsafe {
+            let mut info: libc::signalfd_siginfo = mem::zeroed();
+            let size = mem::size_of::<libc::signalfd_siginfo>();
+            let n = libc::read(fd, &mut info as *mut _ as *mut libc::c_void, size);
+            if n == -1 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(())
+            }
+        })
+    }
+}
+
+impl AsRawFd for Signal {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+impl Drop for Signal {
+    fn drop(&mut self) {
+        self.selector.unregister(self.fd.as_raw_fd());
+    }
+}
+
+pub struct Recv<'a> {
+    signal: &'a mut Signal,
+}
+
+impl Future for Recv<'_> {
+    type Output = io::Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.signal.poll_recv(cx)
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    ffi::c_int,
+    io, mem,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    os::{
+        fd::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+        unix::ffi::OsStrExt,
+    },
+    path::Path,
+    task::{Context, Poll},
+};
+
+use crate::io_selector::IOSelector;
+
+// 논블로킹 소켓을 만들어 connect를 시작한다.
+// 바로 연결되었다면 true를 반환한다.
+pub(crate) fn start_connect(
+    domain: c_int,
+    addr: &libc::sockaddr_storage,
+    len: libc::socklen_t,
+) -> io::Result<(OwnedFd, bool)> {
+    let fd = unsafe {
+        libc::socket(
+            domain,
+            libc::SOCK_STREAM | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+            0,
+        )
+    };
+    if fd == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    // 에러가 발생하면 drop에서 close된다.
+    let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    let ret = unsafe {
+        libc::connect(
+            fd.as_raw_fd(),
+            addr as *const libc::sockaddr_storage as *const libc::sockaddr,
+            len,
+        )
+    };
+    if ret == 0 {
+        return Ok((fd, true));
+    }
+
+    let err = io::Error::last_os_error();
+    if err.raw_os_error() == Some(libc::EINPROGRESS) {
+        Ok((fd, false))
+    } else {
+        Err(err)
+    }
+}
+
+// 연결 중인 소켓이 쓰기 가능해질 때까지 기다리고 연결 결과를 반환한다.
+pub(crate) fn poll_connect(
+    selector: &IOSelector,
+    fd: RawFd,
+    cx: &mut Context<'_>,
+) -> Poll<io::Result<()>> {
+    // 다른 이유로 poll될 수도 있으므로 쓰기 가능한지 직접 확인한다.
+    let mut pfd = libc::pollfd {
+        fd,
+        events: libc::POLLOUT,
+        revents: 0,
+    };
+    if unsafe { libc::poll(&mut pfd, 1, 0) } == -1 {
+        return Poll::Ready(Err(io::Error::last_os_error()));
+    }
+
+    if pfd.revents == 0 {
+        if let Some(err) = selector.take_error(fd) {
+            return Poll::Ready(Err(err));
+        }
+        selector.register(libc::EPOLLOUT, fd, cx.waker().clone());
+        return Poll::Pending;
+    }
+
+    // 연결 결과는 SO_ERROR로 확인한다.
+    let mut err: c_int = 0;
+    let mut len = mem::size_of::<c_int>() as libc::socklen_t;
+    let ret = unsafe {
+        libc::getsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            libc::SO_ERROR,
+            &mut err as *mut c_int as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    if ret == -1 {
+        return Poll::Ready(Err(io::Error::last_os_error()));
+    }
+    if err != 0 {
+        return Poll::Ready(Err(io::Error::from_raw_os_error(err)));
+    }
+
+    Poll::Ready(Ok(()))
+}
+
+pub(crate) fn inet_sockaddr(addr: &SocketAddr) -> (libc::sockaddr_storage, libc::socklen_t) {
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let len = match addr {
+        SocketAddr::V4(addr) => {
+            let sin = unsafe {
+                &mut *(&mut storage as *mut libc::sockaddr_storage as *mut libc::sockaddr_in)
+            };
+            sin.sin_family = libc::AF_INET as libc::sa_family_t;
+            sin.sin_port = addr.port().to_be();
+            sin.sin_addr = libc::in_addr {
+                s_addr: u32::from_ne_bytes(addr.ip().octets()),
+            };
+            mem::size_of::<libc::sockaddr_in>()
+        }
+        SocketAddr::V6(addr) => {
+            let sin6 = unsafe {
+                &mut *(&mut storage as *mut libc::sockaddr_storage as *mut libc::sockaddr_in6)
+            };
+            sin6.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+            sin6.sin6_port = addr.port().to_be();
+            sin6.sin6_flowinfo = addr.flowinfo();
+            sin6.sin6_addr = libc::in6_addr {
+                s6_addr: addr.ip().octets(),
+            };
+            sin6.sin6_scope_id = addr.scope_id();
+            mem::size_of::<libc::sockaddr_in6>()
+        }
+    };
+
+    (storage, len as libc::socklen_t)
+}
+
+pub(crate) fn unix_sockaddr(path: &Path) -> io::Result<(libc::sockaddr_storage, libc::socklen_t)> {
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let sun =
+        unsafe { &mut *(&mut storage as *mut libc::sockaddr_storage as *mut libc::sockaddr_un) };
+    sun.sun_family = libc::AF_UNIX as libc::sa_family_t;
+
+    // 마지막은 NUL 문자
+    let bytes = path.as_os_str().as_bytes();
+    if bytes.len() >= sun.sun_path.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "path must be shorter than SUN_LEN",
+        ));
+    }
+    sun.sun_path
+        .iter_mut()
+        .zip(bytes)
+        .for_each(|(dst, src)| *dst = *src as libc::c_char);
+
+    let len = mem::offset_of!(libc::sockaddr_un, sun_path) + bytes.len() + 1;
+    Ok((storage, len as libc::socklen_t))
+}
+
+// accept 등으로 받은 주소를 SocketAddr로 변환한다.
+pub(crate) fn inet_addr(storage: &libc::sockaddr_storage) -> io::Result<SocketAddr> {
+    match storage.ss_family as c_int {
+        libc::AF_INET => {
+            let sin =
+                unsafe { &*(storage as *const libc::sockaddr_storage as *const libc::sockaddr_in) };
+            let ip = Ipv4Addr::from(sin.sin_addr.s_addr.to_ne_bytes());
+            Ok(SocketAddrV4::new(ip, u16::from_be(sin.sin_port)).into())
+        }
+        libc::AF_INET6 => {
+            let sin6 = unsafe {
+                &*(storage as *const libc::sockaddr_storage as *const libc::sockaddr_in6)
+            };
+            let ip = Ipv6Addr::from(sin6.sin6_addr.s6_addr);
+            Ok(SocketAddrV6::new(
+                ip,
+                u16::from_be(sin6.sin6_port),
+                sin6.sin6_flowinfo,
+                sin6.sin6_scope_id,
+            )
+            .into())
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid address family",
+        )),
+    }
+}
+
+fn cvt(ret: isize) -> io::Result<usize> {
+    if ret == -1 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(ret as usize)
+    }
+}
+
+pub(crate) fn recv(fd: RawFd, buf: &mut [u8]) -> io::Result<usize> {
+    cvt(unsafe { libc::recv(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0) })
+}
+
+// 상대가 닫은 소켓에 써도 SIGPIPE가 발생하지 않도록 한다.
+pub(crate) fn send(fd: RawFd, buf: &[u8]) -> io::Result<usize> {
+    cvt(unsafe {
+        libc::send(
+            fd,
+            buf.as_ptr() as *const libc::c_void,
+            buf.len(),
+            libc::MSG_NOSIGNAL,
+        )
+    })
+}
+
+// 받아들인 소켓은 논블로킹으로 만든다.
+pub(crate) fn accept(fd: RawFd) -> io::Result<(OwnedFd, SocketAddr)> {
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let mut len = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    let ret = unsafe {
+        libc::accept4(
+            fd,
+            &mut storage as *mut libc::sockaddr_storage as *mut libc::sockaddr,
+            &mut len,
+            libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+        )
+    };
+    if ret == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    let stream = unsafe { OwnedFd::from_raw_fd(ret) };
+    Ok((stream, inet_addr(&storage)?))
+}
@@ -0,0 +1,20 @@
+// 태스크를 블록하지 않고 대기하는 동기 처리 기구
+// 대기 중인 태스크는 도착 순서대로 깨어나고, 대기 중인 Future를 파기해도 다른 태스크의 진행을 막지 않는다.
+mod barrier;
+pub mod broadcast;
+mod cancellation_token;
+mod error;
+pub mod mpsc;
+mod mutex;
+mod notify;
+pub mod oneshot;
+mod rwlock;
+mod semaphore;
+pub mod watch;
+
+pub use barrier::{Barrier, BarrierWait, BarrierWaitResult};
+pub use cancellation_token::{CancellationToken, Cancelled};
+pub use mutex::{AsyncMutex, AsyncMutexGuard, Lock};
+pub use notify::{Notified, Notify};
+pub use rwlock::{AsyncRwLock, AsyncRwLockReadGuard, AsyncRwLockWriteGuard, Read, Write};
+pub use semaphore::{Acquire, AsyncSemaphore, SemaphorePermit};
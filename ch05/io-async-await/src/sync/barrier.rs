@@ -0,0 +1,110 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll, Waker},
+};
+
+struct State {
+    // 현재 세대에서 도착한 태스크 수
+    arrived: usize,
+    waiters: Vec<(u64, Waker)>,
+    // 모든 태스크가 도착할 때마다 증가한다.
+    generation: u64,
+    next_id: u64,
+}
+
+// n개의 태스크가 wait를 호출할 때까지 기다린다.
+pub struct Barrier {
+    n: usize,
+    state: Mutex<State>,
+}
+
+impl Barrier {
+    pub fn new(n: usize) -> Self {
+        Barrier {
+            n,
+            state: Mutex::new(State {
+                arrived: 0,
+                waiters: Vec::new(),
+                generation: 0,
+                next_id: 0,
+            }),
+        }
+    }
+
+    // 대기 중인 Future를 파기하면 도착하지 않은 것으로 취급한다.
+    pub fn wait(&self) -> BarrierWait<'_> {
+        BarrierWait {
+            barrier: self,
+            waiting: None,
+        }
+    }
+}
+
+pub struct BarrierWaitResult {
+    leader: bool,
+}
+
+impl BarrierWaitResult {
+    // 각 세대에서 마지막에 도착한 태스크만 true
+    pub fn is_leader(&self) -> bool {
+        self.leader
+    }
+}
+
+pub struct BarrierWait<'a> {
+    barrier: &'a Barrier,
+    // 도착한 세대와 ID
+    waiting: Option<(u64, u64)>,
+}
+
+impl Future for BarrierWait<'_> {
+    type Output = BarrierWaitResult;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let mut state = this.barrier.state.lock().unwrap();
+
+        if let Some((generation, id)) = this.waiting {
+            if state.generation != generation {
+                this.waiting = None;
+                return Poll::Ready(BarrierWaitResult { leader: false });
+            }
+            if let Some((_, waker)) = state.waiters.iter_mut().find(|(i, _)| *i == id) {
+                waker.clone_from(cx.waker());
+            }
+            return Poll::Pending;
+        }
+
+        state.arrived += 1;
+        if state.arrived >= this.barrier.n {
+            state.arrived = 0;
+            state.generation += 1;
+            let waiters = std::mem::take(&mut state.waiters);
+            drop(state);
+            waiters.into_iter().for_each(|(_, waker)| waker.wake());
+            return Poll::Ready(BarrierWaitResult { leader: true });
+        }
+
+        let id = state.next_id;
+        state.next_id += 1;
+        state.waiters.push((id, cx.waker().clone()));
+        this.waiting = Some((state.generation, id));
+        Poll::Pending
+    }
+}
+
+impl Drop for BarrierWait<'_> {
+    fn drop(&mut self) {
+        let Some((generation, id)) = self.waiting else {
+            return;
+        };
+
+        let mut state = self.barrier.state.lock().unwrap();
+        if state.generation == generation {
+            state.arrived -= 1;
+            state.waiters.retain(|(i, _)| *i != id);
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+pub use super::error::SendError;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RecvError {
+    // 모든 Sender가 파기되고 남은 값이 없음
+    Closed,
+    // 버퍼에서 밀려나 받지 못한 값의 수
+    Lagged(u64),
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecvError::Closed => write!(f, "channel closed"),
+            RecvError::Lagged(n) => write!(f, "receiver lagged by {}", n),
+        }
+    }
+}
+
+impl std::error::Error for RecvError {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TryRecvError {
+    Empty,
+    Closed,
+    Lagged(u64),
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => write!(f, "channel empty"),
+            TryRecvError::Closed => write!(f, "channel closed"),
+            TryRecvError::Lagged(n) => write!(f, "receiver lagged by {}", n),
+        }
+    }
+}
+
+impl std::error::Error for TryRecvError {}
+
+struct State<T> {
+    // 최근 cap개의 값
+    buffer: VecDeque<T>,
+    cap: usize,
+    // buffer의 선두 값의 일련번호
+    head: u64,
+    senders: usize,
+    receivers: usize,
+    // 새 값을 기다리는 Receiver의 waker
+    wakers: HashMap<u64, Waker>,
+    next_id: u64,
+}
+
+impl<T> State<T> {
+    // 다음에 보낼 값의 일련번호
+    fn tail(&self) -> u64 {
+        self.head + self.buffer.len() as u64
+    }
+}
+
+impl<T: Clone> State<T> {
+    // next번째 값을 복제하고 next를 진행한다.
+    // 받을 값이 없다면 Closed를 반환한다.
+    fn next_value(&self, next: &mut u64) -> Result<T, RecvError> {
+        if *next < self.head {
+            // 놓친 값은 건너뛰고 가장 오래된 값부터 다시 받는다.
+            let lagged = self.head - *next;
+            *next = self.head;
+            return Err(RecvError::Lagged(lagged));
+        }
+
+        match self.buffer.get((*next - self.head) as usize) {
+            Some(value) => {
+                *next += 1;
+                Ok(value.clone())
+            }
+            None => Err(RecvError::Closed),
+        }
+    }
+}
+
+struct Inner<T> {
+    state: Mutex<State<T>>,
+}
+
+// 보낸 값을 모든 Receiver가 복제해서 받는 채널
+// 송신은 기다리지 않고, 읽기가 늦은 Receiver는 오래된 값을 놓치고 RecvError::Lagged를 받는다.
+pub fn channel<T: Clone>(cap: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(cap > 0);
+    let inner = Arc::new(Inner {
+        state: Mutex::new(State {
+            buffer: VecDeque::with_capacity(cap),
+            cap,
+            head: 0,
+            senders: 1,
+            receivers: 0,
+            wakers: HashMap::new(),
+            next_id: 0,
+        }),
+    });
+    let sender = Sender { inner };
+    let receiver = sender.subscribe();
+    (sender, receiver)
+}
+
+pub struct Sender<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> Sender<T> {
+    // 값을 받을 Receiver의 수를 반환한다.
+    // Receiver가 없다면 값을 돌려준다.
+    pub fn send(&self, value: T) -> Result<usize, SendError<T>> {
+        let (receivers, wakers, old) = {
+            let mut state = self.inner.state.lock().unwrap();
+            if state.receivers == 0 {
+                return Err(SendError(value));
+            }
+
+            state.buffer.push_back(value);
+            let old = if state.buffer.len() > state.cap {
+                state.head += 1;
+                state.buffer.pop_front()
+            } else {
+                None
+            };
+            let wakers = state.wakers.drain().map(|(_, w)| w).collect::<Vec<_>>();
+            (state.receivers, wakers, old)
+        };
+
+        drop(old);
+        wakers.into_iter().for_each(|waker| waker.wake());
+        Ok(receivers)
+    }
+
+    // 이후에 보낸 값을 받는 Receiver를 만든다.
+    pub fn subscribe(&self) -> Receiver<T> {
+        let mut state = self.inner.state.lock().unwrap();
+        state.receivers += 1;
+        let id = state.next_id;
+        state.next_id += 1;
+        Receiver {
+            inner: self.inner.clone(),
+            id,
+            next: state.tail(),
+        }
+    }
+
+    pub fn receiver_count(&self) -> usize {
+        self.inner.state.lock().unwrap().receivers
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.inner.state.lock().unwrap().senders += 1;
+        Sender {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let wakers = {
+            let mut state = self.inner.state.lock().unwrap();
+            state.senders -= 1;
+            if state.senders > 0 {
+                return;
+            }
+            state.wakers.drain().map(|(_, w)| w).collect::<Vec<_>>()
+        };
+        wakers.into_iter().for_each(|waker| waker.wake());
+    }
+}
+
+pub struct Receiver<T> {
+    inner: Arc<Inner<T>>,
+    id: u64,
+    // 다음에 받을 값의 일련번호
+    next: u64,
+}
+
+impl<T: Clone> Receiver<T> {
+    pub fn recv(&mut self) -> Recv<'_, T> {
+        Recv { receiver: self }
+    }
+
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let state = self.inner.state.lock().unwrap();
+        match state.next_value(&mut self.next) {
+            Ok(value) => Ok(value),
+            Err(RecvError::Lagged(n)) => Err(TryRecvError::Lagged(n)),
+            Err(RecvError::Closed) if state.senders == 0 => Err(TryRecvError::Closed),
+            Err(RecvError::Closed) => Err(TryRecvError::Empty),
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.state.lock().unwrap();
+        state.receivers -= 1;
+        state.wakers.remove(&self.id);
+    }
+}
+
+pub struct Recv<'a, T> {
+    receiver: &'a mut Receiver<T>,
+}
+
+impl<T: Clone> Future for Recv<'_, T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let receiver = &mut *self.receiver;
+        let mut state = receiver.inner.state.lock().unwrap();
+        match state.next_value(&mut receiver.next) {
+            Err(RecvError::Closed) if state.senders > 0 => {
+                state.wakers.insert(receiver.id, cx.waker().clone());
+                Poll::Pending
+            }
+            result => Poll::Ready(result),
+        }
+    }
+}
+
+impl<T> Drop for Recv<'_, T> {
+    fn drop(&mut self) {
+        let receiver = &*self.receiver;
+        receiver
+            .inner
+            .state
+            .lock()
+            .unwrap()
+            .wakers
+            .remove(&receiver.id);
+    }
+}
@@ -0,0 +1,111 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, Weak,
+    },
+    task::{Context, Poll},
+};
+
+use super::notify::{Notified, Notify};
+
+struct Inner {
+    cancelled: AtomicBool,
+    notify: Notify,
+    // 부모와 함께 취소되는 자식 토큰
+    children: Mutex<Vec<Weak<Inner>>>,
+}
+
+impl Inner {
+    fn new() -> Self {
+        Inner {
+            cancelled: AtomicBool::new(false),
+            notify: Notify::new(),
+            children: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn cancel(&self) {
+        if self.cancelled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        self.notify.notify_waiters();
+
+        let children = std::mem::take(&mut *self.children.lock().unwrap());
+        children
+            .into_iter()
+            .filter_map(|child| child.upgrade())
+            .for_each(|child| child.cancel());
+    }
+}
+
+// 태스크에 처리의 중단을 알리기 위한 토큰
+// 복제한 토큰은 상태를 공유한다.
+#[derive(Clone)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        CancellationToken {
+            inner: Arc::new(Inner::new()),
+        }
+    }
+
+    // 이 토큰이 취소되면 함께 취소되는 토큰을 만든다.
+    // 자식 토큰을 취소해도 부모는 취소되지 않는다.
+    pub fn child_token(&self) -> CancellationToken {
+        let child = CancellationToken::new();
+
+        let mut children = self.inner.children.lock().unwrap();
+        if self.is_cancelled() {
+            child.cancel();
+        } else {
+            children.retain(|c| c.strong_count() > 0);
+            children.push(Arc::downgrade(&child.inner));
+        }
+
+        child
+    }
+
+    pub fn cancel(&self) {
+        self.inner.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    // 취소될 때까지 기다린다.
+    pub fn cancelled(&self) -> Cancelled<'_> {
+        Cancelled {
+            token: self,
+            // 취소 여부를 확인하기 전에 만들어서 통지를 놓치지 않도록 한다.
+            notified: self.inner.notify.notified(),
+        }
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct Cancelled<'a> {
+    token: &'a CancellationToken,
+    notified: Notified<'a>,
+}
+
+impl Future for Cancelled<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.token.is_cancelled() {
+            return Poll::Ready(());
+        }
+        Pin::new(&mut self.notified).poll(cx)
+    }
+}
@@ -0,0 +1,316 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+pub use super::error::{SendError, TryRecvError, TrySendError};
+
+struct State<T> {
+    queue: VecDeque<T>,
+    cap: usize,
+    // 빈 자리를 기다리는 송신자
+    // 도착 순서대로 자리가 예약되고 큐에서 제거된다.
+    // 닫힌 후에는 예약하지 않으므로 각 송신자가 스스로 제거한다.
+    send_waiters: VecDeque<(u64, Waker)>,
+    // 예약되었지만 아직 값이 들어가지 않은 자리
+    reserved: usize,
+    next_id: u64,
+    senders: usize,
+    // 수신 측이 close 또는 파기됨
+    closed: bool,
+    rx_waker: Option<Waker>,
+}
+
+impl<T> State<T> {
+    // 빈 자리를 선두의 송신자부터 예약하고 깨울 waker를 반환한다.
+    fn reserve(&mut self) -> Vec<Waker> {
+        let mut wakers = Vec::new();
+        while !self.closed && self.queue.len() + self.reserved < self.cap {
+            let Some((_, waker)) = self.send_waiters.pop_front() else {
+                break;
+            };
+            self.reserved += 1;
+            wakers.push(waker);
+        }
+        wakers
+    }
+}
+
+struct Inner<T> {
+    state: Mutex<State<T>>,
+}
+
+// 버퍼가 가득 차면 send가 빈 자리가 생길 때까지 기다리는 채널
+pub fn channel<T>(cap: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(cap > 0);
+    let inner = Arc::new(Inner {
+        state: Mutex::new(State {
+            queue: VecDeque::with_capacity(cap),
+            cap,
+            send_waiters: VecDeque::new(),
+            reserved: 0,
+            next_id: 0,
+            senders: 1,
+            closed: false,
+            rx_waker: None,
+        }),
+    });
+    (
+        Sender {
+            inner: inner.clone(),
+        },
+        Receiver { inner },
+    )
+}
+
+pub struct Sender<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> Sender<T> {
+    pub fn send(&self, value: T) -> Send<'_, T> {
+        Send {
+            sender: self,
+            value: Some(value),
+            wait: Wait::Idle,
+        }
+    }
+
+    // 기다리는 송신자가 있다면 빈 자리가 있어도 Full이 된다.
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        let waker = {
+            let mut state = self.inner.state.lock().unwrap();
+            if state.closed {
+                return Err(TrySendError::Closed(value));
+            }
+            if !state.send_waiters.is_empty() || state.queue.len() + state.reserved >= state.cap {
+                return Err(TrySendError::Full(value));
+            }
+            state.queue.push_back(value);
+            state.rx_waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        Ok(())
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.inner.state.lock().unwrap().closed
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.inner.state.lock().unwrap().senders += 1;
+        Sender {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut state = self.inner.state.lock().unwrap();
+            state.senders -= 1;
+            if state.senders > 0 {
+                return;
+            }
+            state.rx_waker.take()
+        };
+
+        // 마지막 송신자라면 수신 측에 EOF를 알린다.
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+// 송신자의 대기 상태
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Wait {
+    Idle,
+    // 대기열에 들어가 있다.
+    Waiting(u64),
+    // reserve로 자리를 받았다.
+    Reserved,
+}
+
+pub struct Send<'a, T> {
+    sender: &'a Sender<T>,
+    value: Option<T>,
+    wait: Wait,
+}
+
+impl<T> Send<'_, T> {
+    // 대기열에서 제거되었다면 reserve가 자리를 예약한 것이다.
+    fn update(&mut self, state: &State<T>) {
+        if let Wait::Waiting(id) = self.wait {
+            if !state.send_waiters.iter().any(|(i, _)| *i == id) {
+                self.wait = Wait::Reserved;
+            }
+        }
+    }
+
+    // 대기열에서 나오거나 예약한 자리를 되돌린다.
+    fn cancel(&mut self, state: &mut State<T>) {
+        self.update(state);
+        match self.wait {
+            Wait::Idle => (),
+            Wait::Waiting(id) => state.send_waiters.retain(|(i, _)| *i != id),
+            Wait::Reserved => state.reserved -= 1,
+        }
+        self.wait = Wait::Idle;
+    }
+}
+
+// value를 고정해서 사용하지 않으므로 T에 관계없이 Unpin
+impl<T> Unpin for Send<'_, T> {}
+
+impl<T> Future for Send<'_, T> {
+    type Output = Result<(), SendError<T>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let sender = this.sender;
+        let waker = {
+            let mut state = sender.inner.state.lock().unwrap();
+            if state.closed {
+                this.cancel(&mut state);
+                let value = this.value.take().expect("Send polled after completion");
+                return Poll::Ready(Err(SendError(value)));
+            }
+
+            this.update(&state);
+            match this.wait {
+                Wait::Idle
+                    if state.send_waiters.is_empty()
+                        && state.queue.len() + state.reserved < state.cap => {}
+                Wait::Idle => {
+                    let id = state.next_id;
+                    state.next_id += 1;
+                    state.send_waiters.push_back((id, cx.waker().clone()));
+                    this.wait = Wait::Waiting(id);
+                    return Poll::Pending;
+                }
+                Wait::Waiting(id) => {
+                    if let Some((_, waker)) = state.send_waiters.iter_mut().find(|(i, _)| *i == id)
+                    {
+                        waker.clone_from(cx.waker());
+                    }
+                    return Poll::Pending;
+                }
+                Wait::Reserved => {
+                    state.reserved -= 1;
+                    this.wait = Wait::Idle;
+                }
+            }
+
+            let value = this.value.take().expect("Send polled after completion");
+            state.queue.push_back(value);
+            state.rx_waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> Drop for Send<'_, T> {
+    fn drop(&mut self) {
+        if self.wait == Wait::Idle {
+            return;
+        }
+
+        let sender = self.sender;
+        let wakers = {
+            let mut state = sender.inner.state.lock().unwrap();
+            // 예약된 자리는 다음 송신자에게 넘긴다.
+            self.cancel(&mut state);
+            state.reserve()
+        };
+        wakers.into_iter().for_each(|waker| waker.wake());
+    }
+}
+
+pub struct Receiver<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> Receiver<T> {
+    // 모든 Sender가 파기되고 버퍼가 비면 None
+    pub fn recv(&mut self) -> Recv<'_, T> {
+        Recv { receiver: self }
+    }
+
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let (value, wakers) = {
+            let mut state = self.inner.state.lock().unwrap();
+            match state.queue.pop_front() {
+                Some(value) => (value, state.reserve()),
+                None if state.senders == 0 || state.closed => return Err(TryRecvError::Closed),
+                None => return Err(TryRecvError::Empty),
+            }
+        };
+        wakers.into_iter().for_each(|waker| waker.wake());
+        Ok(value)
+    }
+
+    // 이후의 send를 실패시킨다.
+    // 버퍼에 남은 값은 계속 받을 수 있다.
+    // 기다리는 송신자는 깨어난 후에 스스로 대기열에서 나온다.
+    pub fn close(&mut self) {
+        let wakers = {
+            let mut state = self.inner.state.lock().unwrap();
+            state.closed = true;
+            state
+                .send_waiters
+                .iter()
+                .map(|(_, waker)| waker.clone())
+                .collect::<Vec<_>>()
+        };
+        wakers.into_iter().for_each(|waker| waker.wake());
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.close();
+        // 값의 drop에서 채널을 사용할 수 있으므로 락을 해제한 후에 파기한다.
+        let queue = std::mem::take(&mut self.inner.state.lock().unwrap().queue);
+        drop(queue);
+    }
+}
+
+pub struct Recv<'a, T> {
+    receiver: &'a mut Receiver<T>,
+}
+
+impl<T> Future for Recv<'_, T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let inner = &self.receiver.inner;
+        let (value, wakers) = {
+            let mut state = inner.state.lock().unwrap();
+            match state.queue.pop_front() {
+                Some(value) => (value, state.reserve()),
+                None if state.senders == 0 || state.closed => return Poll::Ready(None),
+                None => {
+                    state.rx_waker = Some(cx.waker().clone());
+                    return Poll::Pending;
+                }
+            }
+        };
+
+        wakers.into_iter().for_each(|waker| waker.wake());
+        Poll::Ready(Some(value))
+    }
+}
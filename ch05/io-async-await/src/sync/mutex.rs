@@ -0,0 +1,95 @@
+use std::{
+    cell::UnsafeCell,
+    future::Future,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use super::semaphore::{Acquire, AsyncSemaphore};
+
+// 퍼밋이 1개인 세마포로 구현한 뮤텍스
+// 락을 기다리는 동안 태스크는 워커를 점유하지 않으므로 가드를 가진 채로 await할 수 있다.
+pub struct AsyncMutex<T> {
+    semaphore: AsyncSemaphore,
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Send for AsyncMutex<T> {}
+unsafe impl<T: Send> Sync for AsyncMutex<T> {}
+
+impl<T> AsyncMutex<T> {
+    pub fn new(v: T) -> Self {
+        AsyncMutex {
+            semaphore: AsyncSemaphore::new(1),
+            data: UnsafeCell::new(v),
+        }
+    }
+
+    pub fn lock(&self) -> Lock<'_, T> {
+        Lock {
+            mutex: self,
+            acquire: self.semaphore.acquire(),
+        }
+    }
+
+    pub fn try_lock(&self) -> Option<AsyncMutexGuard<'_, T>> {
+        let permit = self.semaphore.try_acquire()?;
+        permit.forget();
+        Some(AsyncMutexGuard { mutex: self })
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+pub struct Lock<'a, T> {
+    mutex: &'a AsyncMutex<T>,
+    acquire: Acquire<'a>,
+}
+
+impl<'a, T> Future for Lock<'a, T> {
+    type Output = AsyncMutexGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.acquire).poll(cx) {
+            Poll::Ready(permit) => {
+                // 퍼밋은 가드의 drop에서 반환한다.
+                permit.forget();
+                Poll::Ready(AsyncMutexGuard { mutex: self.mutex })
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+pub struct AsyncMutexGuard<'a, T> {
+    mutex: &'a AsyncMutex<T>,
+}
+
+unsafe impl<T: Sync> Sync for AsyncMutexGuard<'_, T> {}
+
+impl<T> Deref for AsyncMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.mutex.data.get() }
+    }
+}
+
+impl<T> DerefMut for AsyncMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.mutex.data.get() }
+    }
+}
+
+impl<T> Drop for AsyncMutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.mutex.semaphore.add_permits(1);
+    }
+}
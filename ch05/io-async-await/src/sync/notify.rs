@@ -0,0 +1,164 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll, Waker},
+};
+
+struct Waiter {
+    id: u64,
+    waker: Waker,
+}
+
+struct State {
+    // 대기자가 없을 때의 notify_one을 다음 notified를 위해 저장한다.
+    permit: bool,
+    waiters: VecDeque<Waiter>,
+    // notify_one으로 깨웠지만 아직 poll되지 않은 대기자
+    notified: Vec<u64>,
+    // notify_waiters가 호출된 횟수
+    generation: u64,
+    next_id: u64,
+}
+
+impl State {
+    fn notify_one(&mut self) -> Option<Waker> {
+        match self.waiters.pop_front() {
+            Some(waiter) => {
+                self.notified.push(waiter.id);
+                Some(waiter.waker)
+            }
+            None => {
+                self.permit = true;
+                None
+            }
+        }
+    }
+}
+
+pub struct Notify {
+    state: Mutex<State>,
+}
+
+impl Notify {
+    pub fn new() -> Self {
+        Notify {
+            state: Mutex::new(State {
+                permit: false,
+                waiters: VecDeque::new(),
+                notified: Vec::new(),
+                generation: 0,
+                next_id: 0,
+            }),
+        }
+    }
+
+    // 가장 오래 기다린 태스크를 하나 깨운다.
+    // 기다리는 태스크가 없다면 다음 notified가 바로 완료된다.
+    pub fn notify_one(&self) {
+        let waker = self.state.lock().unwrap().notify_one();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    // 현재 기다리고 있는 태스크와 notified로 만들어진 Future를 모두 깨운다.
+    pub fn notify_waiters(&self) {
+        let waiters = {
+            let mut state = self.state.lock().unwrap();
+            state.generation += 1;
+            state.waiters.drain(..).collect::<Vec<_>>()
+        };
+        waiters.into_iter().for_each(|waiter| waiter.waker.wake());
+    }
+
+    // 만들어진 시점 이후의 notify_waiters를 놓치지 않는다.
+    pub fn notified(&self) -> Notified<'_> {
+        Notified {
+            notify: self,
+            generation: self.state.lock().unwrap().generation,
+            id: None,
+        }
+    }
+}
+
+impl Default for Notify {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct Notified<'a> {
+    notify: &'a Notify,
+    generation: u64,
+    // 대기열에 들어가 있다면 그 ID
+    id: Option<u64>,
+}
+
+impl Future for Notified<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let mut state = this.notify.state.lock().unwrap();
+
+        match this.id {
+            None => {
+                if state.generation != this.generation {
+                    return Poll::Ready(());
+                }
+                if state.permit {
+                    state.permit = false;
+                    return Poll::Ready(());
+                }
+
+                let id = state.next_id;
+                state.next_id += 1;
+                state.waiters.push_back(Waiter {
+                    id,
+                    waker: cx.waker().clone(),
+                });
+                this.id = Some(id);
+                Poll::Pending
+            }
+            Some(id) => {
+                if let Some(waiter) = state.waiters.iter_mut().find(|w| w.id == id) {
+                    waiter.waker.clone_from(cx.waker());
+                    return Poll::Pending;
+                }
+
+                // 대기열에서 제거되었다면 통지를 받았다.
+                state.notified.retain(|n| *n != id);
+                this.id = None;
+                Poll::Ready(())
+            }
+        }
+    }
+}
+
+impl Drop for Notified<'_> {
+    fn drop(&mut self) {
+        let Some(id) = self.id else {
+            return;
+        };
+
+        let waker = {
+            let mut state = self.notify.state.lock().unwrap();
+            if let Some(pos) = state.waiters.iter().position(|w| w.id == id) {
+                state.waiters.remove(pos);
+                None
+            } else if let Some(pos) = state.notified.iter().position(|n| *n == id) {
+                // 받지 않은 notify_one을 다음 대기자에게 넘긴다.
+                state.notified.swap_remove(pos);
+                state.notify_one()
+            } else {
+                None
+            }
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
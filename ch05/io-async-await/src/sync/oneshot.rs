@@ -0,0 +1,161 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+pub use super::error::{RecvError, TryRecvError};
+
+struct State<T> {
+    value: Option<T>,
+    // 송신 측이 send 또는 파기됨
+    complete: bool,
+    // 수신 측이 close 또는 파기됨
+    closed: bool,
+    rx_waker: Option<Waker>,
+    tx_waker: Option<Waker>,
+}
+
+struct Inner<T> {
+    state: Mutex<State<T>>,
+}
+
+// 값을 한 번만 보낼 수 있는 채널
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let inner = Arc::new(Inner {
+        state: Mutex::new(State {
+            value: None,
+            complete: false,
+            closed: false,
+            rx_waker: None,
+            tx_waker: None,
+        }),
+    });
+    (
+        Sender {
+            inner: inner.clone(),
+        },
+        Receiver { inner },
+    )
+}
+
+pub struct Sender<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> Sender<T> {
+    // 수신 측이 이미 닫혔다면 값을 돌려준다.
+    pub fn send(self, value: T) -> Result<(), T> {
+        let waker = {
+            let mut state = self.inner.state.lock().unwrap();
+            if state.closed {
+                return Err(value);
+            }
+            state.value = Some(value);
+            state.complete = true;
+            state.rx_waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        Ok(())
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.inner.state.lock().unwrap().closed
+    }
+
+    // 수신 측이 닫힐 때까지 기다린다.
+    // 결과가 필요 없어진 처리를 중단하는 데 사용한다.
+    pub fn closed(&mut self) -> Closed<'_, T> {
+        Closed { sender: self }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut state = self.inner.state.lock().unwrap();
+            if state.complete {
+                return;
+            }
+            state.complete = true;
+            state.rx_waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+pub struct Closed<'a, T> {
+    sender: &'a mut Sender<T>,
+}
+
+impl<T> Future for Closed<'_, T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.sender.inner.state.lock().unwrap();
+        if state.closed {
+            return Poll::Ready(());
+        }
+        state.tx_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+// await하면 송신된 값을 받는다.
+// 값을 보내지 않고 Sender가 파기되면 RecvError가 된다.
+pub struct Receiver<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> Receiver<T> {
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let mut state = self.inner.state.lock().unwrap();
+        match state.value.take() {
+            Some(value) => Ok(value),
+            None if state.complete => Err(TryRecvError::Closed),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    // 이후의 send를 실패시킨다.
+    pub fn close(&mut self) {
+        let waker = {
+            let mut state = self.inner.state.lock().unwrap();
+            state.closed = true;
+            state.tx_waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Future for Receiver<T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.inner.state.lock().unwrap();
+        match state.value.take() {
+            Some(value) => Poll::Ready(Ok(value)),
+            None if state.complete => Poll::Ready(Err(RecvError)),
+            None => {
+                state.rx_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
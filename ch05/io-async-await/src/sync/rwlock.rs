@@ -0,0 +1,147 @@
+use std::{
+    cell::UnsafeCell,
+    future::Future,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use super::semaphore::{Acquire, AsyncSemaphore};
+
+// 동시에 락을 획득할 수 있는 Reader의 최대 수
+const MAX_READS: usize = u32::MAX as usize >> 3;
+
+// Reader는 퍼밋을 1개, Writer는 전부 획득한다.
+// 세마포의 대기열이 FIFO이므로 Writer가 기다리는 동안 뒤에 온 Reader는 락을 획득하지 않는다.
+pub struct AsyncRwLock<T> {
+    semaphore: AsyncSemaphore,
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Send for AsyncRwLock<T> {}
+unsafe impl<T: Send + Sync> Sync for AsyncRwLock<T> {}
+
+impl<T> AsyncRwLock<T> {
+    pub fn new(v: T) -> Self {
+        AsyncRwLock {
+            semaphore: AsyncSemaphore::new(MAX_READS),
+            data: UnsafeCell::new(v),
+        }
+    }
+
+    pub fn read(&self) -> Read<'_, T> {
+        Read {
+            lock: self,
+            acquire: self.semaphore.acquire(),
+        }
+    }
+
+    pub fn write(&self) -> Write<'_, T> {
+        Write {
+            lock: self,
+            acquire: self.semaphore.acquire_many(MAX_READS),
+        }
+    }
+
+    pub fn try_read(&self) -> Option<AsyncRwLockReadGuard<'_, T>> {
+        let permit = self.semaphore.try_acquire()?;
+        permit.forget();
+        Some(AsyncRwLockReadGuard { lock: self })
+    }
+
+    pub fn try_write(&self) -> Option<AsyncRwLockWriteGuard<'_, T>> {
+        let permit = self.semaphore.try_acquire_many(MAX_READS)?;
+        permit.forget();
+        Some(AsyncRwLockWriteGuard { lock: self })
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+pub struct Read<'a, T> {
+    lock: &'a AsyncRwLock<T>,
+    acquire: Acquire<'a>,
+}
+
+impl<'a, T> Future for Read<'a, T> {
+    type Output = AsyncRwLockReadGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.acquire).poll(cx) {
+            Poll::Ready(permit) => {
+                permit.forget();
+                Poll::Ready(AsyncRwLockReadGuard { lock: self.lock })
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+pub struct Write<'a, T> {
+    lock: &'a AsyncRwLock<T>,
+    acquire: Acquire<'a>,
+}
+
+impl<'a, T> Future for Write<'a, T> {
+    type Output = AsyncRwLockWriteGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.acquire).poll(cx) {
+            Poll::Ready(permit) => {
+                permit.forget();
+                Poll::Ready(AsyncRwLockWriteGuard { lock: self.lock })
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+pub struct AsyncRwLockReadGuard<'a, T> {
+    lock: &'a AsyncRwLock<T>,
+}
+
+impl<T> Deref for AsyncRwLockReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T> Drop for AsyncRwLockReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.semaphore.add_permits(1);
+    }
+}
+
+pub struct AsyncRwLockWriteGuard<'a, T> {
+    lock: &'a AsyncRwLock<T>,
+}
+
+unsafe impl<T: Sync> Sync for AsyncRwLockWriteGuard<'_, T> {}
+
+impl<T> Deref for AsyncRwLockWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T> DerefMut for AsyncRwLockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.lock.data.get() }
+    }
+}
+
+impl<T> Drop for AsyncRwLockWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.semaphore.add_permits(MAX_READS);
+    }
+}
@@ -0,0 +1,198 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll, Waker},
+};
+
+struct Waiter {
+    id: u64,
+    // 필요한 퍼밋 수
+    needed: usize,
+    waker: Waker,
+}
+
+struct State {
+    permits: usize,
+    // 도착 순서대로 줄을 선 대기자
+    // 퍼밋이 할당되면 큐에서 제거된다.
+    waiters: VecDeque<Waiter>,
+    next_id: u64,
+}
+
+impl State {
+    // 선두부터 순서대로 퍼밋을 할당하고 깨울 waker를 반환한다.
+    // 선두에 할당할 수 없다면 뒤의 대기자가 적은 수를 요구하더라도 할당하지 않는다.
+    fn assign(&mut self) -> Vec<Waker> {
+        let mut wakers = Vec::new();
+        while let Some(waiter) = self.waiters.front() {
+            if waiter.needed > self.permits {
+                break;
+            }
+            self.permits -= waiter.needed;
+            wakers.push(self.waiters.pop_front().unwrap().waker);
+        }
+        wakers
+    }
+}
+
+pub struct AsyncSemaphore {
+    state: Mutex<State>,
+}
+
+impl AsyncSemaphore {
+    pub fn new(permits: usize) -> Self {
+        AsyncSemaphore {
+            state: Mutex::new(State {
+                permits,
+                waiters: VecDeque::new(),
+                next_id: 0,
+            }),
+        }
+    }
+
+    pub fn available_permits(&self) -> usize {
+        self.state.lock().unwrap().permits
+    }
+
+    pub fn acquire(&self) -> Acquire<'_> {
+        self.acquire_many(1)
+    }
+
+    pub fn acquire_many(&self, n: usize) -> Acquire<'_> {
+        Acquire {
+            semaphore: self,
+            needed: n,
+            id: None,
+        }
+    }
+
+    // 대기자가 있는 경우에는 퍼밋이 남아 있어도 실패한다.
+    pub fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        self.try_acquire_many(1)
+    }
+
+    pub fn try_acquire_many(&self, n: usize) -> Option<SemaphorePermit<'_>> {
+        let mut state = self.state.lock().unwrap();
+        if state.waiters.is_empty() && state.permits >= n {
+            state.permits -= n;
+            Some(SemaphorePermit {
+                semaphore: self,
+                permits: n,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn add_permits(&self, n: usize) {
+        let wakers = {
+            let mut state = self.state.lock().unwrap();
+            state.permits += n;
+            state.assign()
+        };
+        wakers.into_iter().for_each(|waker| waker.wake());
+    }
+}
+
+pub struct Acquire<'a> {
+    semaphore: &'a AsyncSemaphore,
+    needed: usize,
+    // 대기열에 들어가 있다면 그 ID
+    id: Option<u64>,
+}
+
+impl<'a> Future for Acquire<'a> {
+    type Output = SemaphorePermit<'a>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let mut state = this.semaphore.state.lock().unwrap();
+
+        let acquired = match this.id {
+            None if state.waiters.is_empty() && state.permits >= this.needed => {
+                state.permits -= this.needed;
+                true
+            }
+            None => {
+                let id = state.next_id;
+                state.next_id += 1;
+                state.waiters.push_back(Waiter {
+                    id,
+                    needed: this.needed,
+                    waker: cx.waker().clone(),
+                });
+                this.id = Some(id);
+                false
+            }
+            // 대기열에서 제거되었다면 퍼밋이 할당되어 있다.
+            Some(id) => match state.waiters.iter_mut().find(|w| w.id == id) {
+                Some(waiter) => {
+                    waiter.waker.clone_from(cx.waker());
+                    false
+                }
+                None => {
+                    this.id = None;
+                    true
+                }
+            },
+        };
+
+        if acquired {
+            Poll::Ready(SemaphorePermit {
+                semaphore: this.semaphore,
+                permits: this.needed,
+            })
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for Acquire<'_> {
+    fn drop(&mut self) {
+        let Some(id) = self.id else {
+            return;
+        };
+
+        let wakers = {
+            let mut state = self.semaphore.state.lock().unwrap();
+            match state.waiters.iter().position(|w| w.id == id) {
+                // 선두가 빠지면 뒤의 대기자에게 할당할 수 있을지도 모른다.
+                Some(pos) => {
+                    state.waiters.remove(pos);
+                }
+                // 할당되었지만 받지 않은 퍼밋을 반환한다.
+                None => state.permits += self.needed,
+            }
+            state.assign()
+        };
+        wakers.into_iter().for_each(|waker| waker.wake());
+    }
+}
+
+// 파기될 때 퍼밋을 반환한다.
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a AsyncSemaphore,
+    permits: usize,
+}
+
+impl SemaphorePermit<'_> {
+    pub fn num_permits(&self) -> usize {
+        self.permits
+    }
+
+    // 퍼밋을 반환하지 않고 버린다.
+    pub fn forget(mut self) {
+        self.permits = 0;
+    }
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        if self.permits > 0 {
+            self.semaphore.add_permits(self.permits);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, RwLock, RwLockReadGuard},
+    task::{Context, Poll, Waker},
+};
+
+pub use super::error::{RecvError, SendError};
+
+struct State {
+    // 값이 갱신될 때마다 증가한다.
+    version: u64,
+    // 변경을 기다리는 Receiver의 waker
+    wakers: HashMap<u64, Waker>,
+    sender_alive: bool,
+    receivers: usize,
+    next_id: u64,
+}
+
+struct Inner<T> {
+    value: RwLock<T>,
+    state: Mutex<State>,
+}
+
+impl<T> Inner<T> {
+    fn new_receiver(self: &Arc<Self>, state: &mut State) -> Receiver<T> {
+        state.receivers += 1;
+        let id = state.next_id;
+        state.next_id += 1;
+        Receiver {
+            inner: self.clone(),
+            id,
+            seen: state.version,
+        }
+    }
+}
+
+// 최신 값 하나만 보관하고 갱신을 Receiver에 알리는 채널
+// 설정의 변경이나 종료 플래그를 전달하는 데 사용한다.
+pub fn channel<T>(init: T) -> (Sender<T>, Receiver<T>) {
+    let inner = Arc::new(Inner {
+        value: RwLock::new(init),
+        state: Mutex::new(State {
+            version: 0,
+            wakers: HashMap::new(),
+            sender_alive: true,
+            receivers: 0,
+            next_id: 0,
+        }),
+    });
+    let receiver = inner.new_receiver(&mut inner.state.lock().unwrap());
+    (Sender { inner }, receiver)
+}
+
+pub struct Sender<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> Sender<T> {
+    // Receiver가 없다면 값을 돌려준다.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        if self.inner.state.lock().unwrap().receivers == 0 {
+            return Err(SendError(value));
+        }
+
+        let old = std::mem::replace(&mut *self.inner.value.write().unwrap(), value);
+        self.notify();
+        drop(old);
+        Ok(())
+    }
+
+    // Receiver의 유무에 관계없이 값을 변경하고 통지한다.
+    pub fn send_modify(&self, modify: impl FnOnce(&mut T)) {
+        modify(&mut self.inner.value.write().unwrap());
+        self.notify();
+    }
+
+    fn notify(&self) {
+        let wakers = {
+            let mut state = self.inner.state.lock().unwrap();
+            state.version += 1;
+            state.wakers.drain().map(|(_, w)| w).collect::<Vec<_>>()
+        };
+        wakers.into_iter().for_each(|waker| waker.wake());
+    }
+
+    pub fn borrow(&self) -> RwLockReadGuard<'_, T> {
+        self.inner.value.read().unwrap()
+    }
+
+    // 현재 값을 본 것으로 하는 Receiver를 만든다.
+    pub fn subscribe(&self) -> Receiver<T> {
+        self.inner
+            .new_receiver(&mut self.inner.state.lock().unwrap())
+    }
+
+    pub fn receiver_count(&self) -> usize {
+        self.inner.state.lock().unwrap().receivers
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let wakers = {
+            let mut state = self.inner.state.lock().unwrap();
+            state.sender_alive = false;
+            state.wakers.drain().map(|(_, w)| w).collect::<Vec<_>>()
+        };
+        wakers.into_iter().for_each(|waker| waker.wake());
+    }
+}
+
+pub struct Receiver<T> {
+    inner: Arc<Inner<T>>,
+    id: u64,
+    // 마지막으로 본 값의 버전
+    seen: u64,
+}
+
+impl<T> Receiver<T> {
+    // 가드를 가진 동안 Sender는 값을 갱신할 수 없으므로 await를 사이에 두지 않는다.
+    pub fn borrow(&self) -> RwLockReadGuard<'_, T> {
+        self.inner.value.read().unwrap()
+    }
+
+    // 값을 본 것으로 하고 가드를 반환한다.
+    pub fn borrow_and_update(&mut self) -> RwLockReadGuard<'_, T> {
+        let value = self.inner.value.read().unwrap();
+        self.seen = self.inner.state.lock().unwrap().version;
+        value
+    }
+
+    pub fn has_changed(&self) -> Result<bool, RecvError> {
+        let state = self.inner.state.lock().unwrap();
+        if state.version != self.seen {
+            Ok(true)
+        } else if state.sender_alive {
+            Ok(false)
+        } else {
+            Err(RecvError)
+        }
+    }
+
+    // 마지막으로 본 후에 값이 갱신될 때까지 기다린다.
+    // 갱신되지 않은 채로 Sender가 파기되면 RecvError가 된다.
+    pub fn changed(&mut self) -> Changed<'_, T> {
+        Changed { receiver: self }
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        let mut receiver = self
+            .inner
+            .new_receiver(&mut self.inner.state.lock().unwrap());
+        receiver.seen = self.seen;
+        receiver
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.state.lock().unwrap();
+        state.receivers -= 1;
+        state.wakers.remove(&self.id);
+    }
+}
+
+pub struct Changed<'a, T> {
+    receiver: &'a mut Receiver<T>,
+}
+
+impl<T> Future for Changed<'_, T> {
+    type Output = Result<(), RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let receiver = &mut *self.receiver;
+        let mut state = receiver.inner.state.lock().unwrap();
+        if state.version != receiver.seen {
+            receiver.seen = state.version;
+            Poll::Ready(Ok(()))
+        } else if !state.sender_alive {
+            Poll::Ready(Err(RecvError))
+        } else {
+            state.wakers.insert(receiver.id, cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl<T> Drop for Changed<'_, T> {
+    fn drop(&mut self) {
+        let receiver = &*self.receiver;
+        receiver
+            .inner
+            .state
+            .lock()
+            .unwrap()
+            .wakers
+            .remove(&receiver.id);
+    }
+}
@@ -0,0 +1,221 @@
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+    time::{Duration, Instant},
+};
+
+use crate::io_selector::IOSelector;
+
+// IOSelector의 타이머 힙에 등록되는 타이머
+pub(crate) struct Timer {
+    waker: Mutex<Option<Waker>>,
+    // 기한이 지나서 깨웠다면 true
+    fired: AtomicBool,
+    // Sleep이 버렸다면 true. 리액터는 힙에서 제거한다.
+    cancelled: AtomicBool,
+}
+
+impl Timer {
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn fire(&self) {
+        self.fired.store(true, Ordering::Release);
+        if let Some(waker) = self.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+}
+
+pub struct Sleep {
+    deadline: Instant,
+    timer: Option<Arc<Timer>>,
+    selector: Arc<IOSelector>,
+}
+
+pub fn sleep(dur: Duration, selector: Arc<IOSelector>) -> Sleep {
+    sleep_until(Instant::now() + dur, selector)
+}
+
+pub fn sleep_until(deadline: Instant, selector: Arc<IOSelector>) -> Sleep {
+    Sleep {
+        deadline,
+        timer: None,
+        selector,
+    }
+}
+
+impl Sleep {
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    pub fn is_elapsed(&self) -> bool {
+        Instant::now() >= self.deadline
+    }
+
+    // 기한을 변경한다.
+    // 이전에 등록한 타이머는 버리고 다음 poll에서 다시 등록한다.
+    pub fn reset(&mut self, deadline: Instant) {
+        self.deadline = deadline;
+        self.cancel();
+    }
+
+    fn cancel(&mut self) {
+        if let Some(timer) = self.timer.take() {
+            timer.waker.lock().unwrap().take();
+            if !timer.fired.load(Ordering::Acquire) {
+                timer.cancelled.store(true, Ordering::Relaxed);
+                self.selector.cancel_timer(&timer);
+            }
+        }
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    // IOSelector가 종료한 후에는 기한을 기다릴 수 없으므로 바로 완료한다.
+    // timeout은 기한이 지난 것으로 에러를 반환한다.
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.is_elapsed() || self.selector.is_shutdown() {
+            self.cancel();
+            return Poll::Ready(());
+        }
+
+        match &self.timer {
+            Some(timer) if !timer.fired.load(Ordering::Acquire) => {
+                // 이미 등록되어 있다면 waker만 교체
+                *timer.waker.lock().unwrap() = Some(cx.waker().clone());
+            }
+            _ => {
+                let timer = Arc::new(Timer {
+                    waker: Mutex::new(Some(cx.waker().clone())),
+                    fired: AtomicBool::new(false),
+                    cancelled: AtomicBool::new(false),
+                });
+                self.selector.add_timer(self.deadline, timer.clone());
+                self.timer = Some(timer);
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        // 리액터에서 타이머를 제거하고 태스크를 참조하지 않도록 waker를 파기
+        self.cancel();
+    }
+}
+
+// 일정한 주기로 완료되는 타이머
+// 놓친 틱은 건너뛰고 현재 시각부터 다시 주기를 센다.
+pub struct Interval {
+    period: Duration,
+    sleep: Sleep,
+}
+
+pub fn interval(period: Duration, selector: Arc<IOSelector>) -> Interval {
+    interval_at(Instant::now(), period, selector)
+}
+
+pub fn interval_at(start: Instant, period: Duration, selector: Arc<IOSelector>) -> Interval {
+    assert!(!period.is_zero(), "interval period must be non-zero");
+    Interval {
+        period,
+        sleep: sleep_until(start, selector),
+    }
+}
+
+impl Interval {
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    pub fn tick(&mut self) -> Tick<'_> {
+        Tick { interval: self }
+    }
+
+    pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Instant> {
+        if Pin::new(&mut self.sleep).poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        let tick = self.sleep.deadline();
+        let now = Instant::now();
+        let mut next = tick + self.period;
+        if next <= now {
+            next = now + self.period;
+        }
+        self.sleep.reset(next);
+
+        Poll::Ready(tick)
+    }
+}
+
+pub struct Tick<'a> {
+    interval: &'a mut Interval,
+}
+
+impl Future for Tick<'_> {
+    type Output = Instant;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.interval.poll_tick(cx)
+    }
+}
+
+// 기한 안에 완료되지 않은 경우의 에러
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed(());
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "deadline has elapsed")
+    }
+}
+
+impl std::error::Error for Elapsed {}
+
+pub struct Timeout<F> {
+    future: Pin<Box<F>>,
+    sleep: Sleep,
+}
+
+pub fn timeout<F: Future>(dur: Duration, future: F, selector: Arc<IOSelector>) -> Timeout<F> {
+    Timeout {
+        future: Box::pin(future),
+        sleep: sleep(dur, selector),
+    }
+}
+
+impl<F> Timeout<F> {
+    pub fn into_inner(self) -> Pin<Box<F>> {
+        self.future
+    }
+}
+
+impl<F: Future> Future for Timeout<F> {
+    type Output = Result<F::Output, Elapsed>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // 기한과 동시에 완료된 경우에는 결과를 우선한다.
+        if let Poll::Ready(v) = self.future.as_mut().poll(cx) {
+            return Poll::Ready(Ok(v));
+        }
+
+        match Pin::new(&mut self.sleep).poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(Elapsed(()))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,6 @@
+// 알고리즘은 raw_lock의 RawAndersonLock을 사용한다.
+// 슬롯 수를 지정할 때는 Lock::with_raw(RawAndersonLock::new(num_slot), v)로 만든다.
+use raw_lock::LockGuard;
+pub use raw_lock::{AndersonLock, RawAndersonLock};
+
+pub type AndersonLockGuard<'a, T> = LockGuard<'a, RawAndersonLock, T>;
@@ -0,0 +1,28 @@
+use std::{sync::Arc, thread};
+
+use anderson_lock::{AndersonLock, RawAndersonLock};
+
+const NUM_LOOP: usize = 100_000;
+const NUM_THREAD: usize = 8;
+// 스레드 수보다 적게 해서 빈 슬롯을 기다리는 경우도 확인한다.
+const NUM_SLOT: usize = 4;
+
+fn main() {
+    let n = Arc::new(AndersonLock::with_raw(RawAndersonLock::new(NUM_SLOT), 0));
+    (0..NUM_THREAD)
+        .map(|_| {
+            let n0 = n.clone();
+            thread::spawn(move || {
+                (0..NUM_LOOP).for_each(|_| {
+                    let mut r = n0.lock();
+                    *r += 1;
+                });
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .for_each(|t| t.join().unwrap());
+
+    let r = n.lock();
+    println!("COUNT = {} (expected = {})", *r, NUM_LOOP * NUM_THREAD);
+}
@@ -0,0 +1,5 @@
+// 알고리즘은 raw_lock의 RawCLHLock을 사용한다.
+use raw_lock::LockGuard;
+pub use raw_lock::{CLHLock, RawCLHLock};
+
+pub type CLHLockGuard<'a, T> = LockGuard<'a, RawCLHLock, T>;
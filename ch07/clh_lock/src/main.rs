@@ -0,0 +1,24 @@
+use std::{sync::Arc, thread};
+
+const NUM_LOOP: usize = 100_000;
+const NUM_THREAD: usize = 4;
+
+fn main() {
+    let n = Arc::new(clh_lock::CLHLock::new(0));
+    (0..NUM_THREAD)
+        .map(|_| {
+            let n0 = n.clone();
+            thread::spawn(move || {
+                (0..NUM_LOOP).for_each(|_| {
+                    let mut r = n0.lock();
+                    *r += 1;
+                });
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .for_each(|t| t.join().unwrap());
+
+    let r = n.lock();
+    println!("COUNT = {} (expected = {})", *r, NUM_LOOP * NUM_THREAD);
+}
@@ -0,0 +1,6 @@
+// 알고리즘은 raw_lock의 RawCohortLock을 사용한다.
+// 노드 수를 지정할 때는 Lock::with_raw(RawCohortLock::with_nodes(num_node), v)로 만든다.
+use raw_lock::LockGuard;
+pub use raw_lock::{current_node, num_nodes, CohortLock, RawCohortLock};
+
+pub type CohortLockGuard<'a, T> = LockGuard<'a, RawCohortLock, T>;
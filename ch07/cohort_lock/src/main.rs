@@ -0,0 +1,55 @@
+use std::{sync::Arc, thread};
+
+use cohort_lock::{CohortLock, RawCohortLock};
+
+const NUM_LOOP: usize = 100_000;
+const NUM_THREAD: usize = 4;
+// 소켓이 하나인 환경에서도 확인할 수 있도록 스레드를 2개의 노드에 나눈다.
+const NUM_NODE: usize = 2;
+
+struct Counter {
+    count: usize,
+    // 마지막으로 락을 획득한 스레드의 노드
+    last_node: usize,
+    // 다른 노드로 락이 넘어간 횟수
+    handoff: usize,
+}
+
+fn main() {
+    println!(
+        "node = {} / {}",
+        cohort_lock::current_node(),
+        cohort_lock::num_nodes()
+    );
+
+    let n = Arc::new(CohortLock::with_raw(
+        RawCohortLock::with_nodes(NUM_NODE),
+        Counter {
+            count: 0,
+            last_node: 0,
+            handoff: 0,
+        },
+    ));
+    (0..NUM_THREAD)
+        .map(|i| {
+            let n0 = n.clone();
+            thread::spawn(move || {
+                let node = i % NUM_NODE;
+                (0..NUM_LOOP).for_each(|_| {
+                    let mut r = n0.lock_on(node);
+                    r.count += 1;
+                    if r.last_node != node {
+                        r.last_node = node;
+                        r.handoff += 1;
+                    }
+                });
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .for_each(|t| t.join().unwrap());
+
+    let r = n.lock();
+    println!("COUNT = {} (expected = {})", r.count, NUM_LOOP * NUM_THREAD);
+    println!("handoff between nodes = {}", r.handoff);
+}
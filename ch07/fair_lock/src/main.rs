@@ -1,16 +1,28 @@
-use std::sync::Arc;
+use std::sync::{Arc, Barrier};
+
+use fair_lock::{FairLock, RawFairLock};
 
 const NUM_LOOP: usize = 100_000;
 const NUM_THREAD: usize = 4;
 
 fn main() {
-    let lock = Arc::new(fair_lock::FairLock::new(0));
+    let lock = Arc::new(FairLock::with_raw(RawFairLock::new(NUM_THREAD), 0));
     (0..NUM_THREAD)
         .map(|i| {
             let lock0 = lock.clone();
             std::thread::spawn(move || {
-                (0..NUM_LOOP).for_each(|_| {
-                    let mut data = lock0.lock(i);
+                (0..NUM_LOOP).for_each(|n| {
+                    // 일부는 try_lock으로 획득해 본다.
+                    let mut data = if (i + n).is_multiple_of(4) {
+                        loop {
+                            if let Some(data) = lock0.try_lock() {
+                                break data;
+                            }
+                            std::hint::spin_loop();
+                        }
+                    } else {
+                        lock0.lock()
+                    };
                     *data += 1;
                 });
             })
@@ -19,9 +31,83 @@ fn main() {
         .into_iter()
         .for_each(|t| t.join().unwrap());
 
+    // 종료한 스레드의 슬롯은 되돌려졌으므로 메인 스레드도 획득할 수 있다.
     println!(
         "COUNT = {} (expected = {})",
-        *lock.lock(0),
+        *lock.lock(),
         NUM_LOOP * NUM_THREAD
     );
+
+    for stat in lock.fairness() {
+        println!(
+            "{:?}: acquired = {}, max bypass = {}",
+            stat.thread, stat.acquired, stat.max_bypass
+        );
+    }
+
+    // 슬롯 수보다 많은 스레드를 차례로 실행해도 슬롯을 빌릴 수 있다.
+    let lock = Arc::new(FairLock::with_raw(RawFairLock::new(2), 0));
+    (0..NUM_THREAD * 2)
+        .map(|_| {
+            let lock0 = lock.clone();
+            std::thread::spawn(move || {
+                (0..1000).for_each(|_| *lock0.lock() += 1);
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .for_each(|t| t.join().unwrap());
+    println!(
+        "COUNT = {} (expected = {}) with {} slots",
+        *lock.lock(),
+        1000 * NUM_THREAD * 2,
+        lock.num_participants()
+    );
+    // 슬롯 수보다 많은 스레드가 동시에 살아 있으면 슬롯을 빌리지 못한 스레드는 슬롯 없이 획득한다.
+    // 이 스레드의 공평성은 보장되지 않으며 fairness에도 나타나지 않는다.
+    let lock = Arc::new(FairLock::with_raw(RawFairLock::new(2), 0));
+    let barrier = Arc::new(Barrier::new(NUM_THREAD));
+    (0..NUM_THREAD)
+        .map(|_| {
+            let lock0 = lock.clone();
+            let barrier = barrier.clone();
+            std::thread::spawn(move || {
+                (0..1000).for_each(|_| *lock0.lock() += 1);
+                // 모든 스레드가 끝날 때까지 슬롯을 되돌리지 않는다.
+                barrier.wait();
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .for_each(|t| t.join().unwrap());
+    println!(
+        "COUNT = {} (expected = {}) with {} slots",
+        *lock.lock(),
+        1000 * NUM_THREAD,
+        lock.num_participants()
+    );
+
+    // release_slot으로 슬롯을 되돌리면 다른 스레드가 슬롯을 빌릴 수 있다.
+    let lock = Arc::new(FairLock::with_raw(RawFairLock::new(2), 0));
+    let barrier = Arc::new(Barrier::new(NUM_THREAD));
+    (0..NUM_THREAD)
+        .map(|_| {
+            let lock0 = lock.clone();
+            let barrier = barrier.clone();
+            std::thread::spawn(move || {
+                (0..1000).for_each(|_| *lock0.lock() += 1);
+                lock0.release_slot();
+                // 모든 스레드가 끝날 때까지 종료하지 않는다.
+                barrier.wait();
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .for_each(|t| t.join().unwrap());
+    println!(
+        "COUNT = {} (expected = {}) with {} slots and release_slot",
+        *lock.lock(),
+        1000 * NUM_THREAD,
+        lock.num_participants()
+    );
 }
@@ -0,0 +1,148 @@
+use std::{
+    cell::UnsafeCell,
+    hint,
+    mem::MaybeUninit,
+    sync::atomic::{fence, AtomicUsize, Ordering},
+};
+
+struct Slot<T> {
+    // 이 슬롯에 다음으로 쓸 수 있는 위치
+    // push할 수 있다면 pos, pop할 수 있다면 pos + 1, pop된 후에는 pos + 용량이 된다.
+    seq: AtomicUsize,
+    data: UnsafeCell<MaybeUninit<T>>,
+}
+
+// Vyukov의 유한 MPMC 큐
+// 위치를 CAS로 확보한 스레드만 그 슬롯에 접근하고 seq로 값이 준비되었음을 알린다.
+// 노드를 할당하지 않으므로 메모리 회수가 필요 없다.
+pub struct ArrayQueue<T> {
+    buffer: Box<[Slot<T>]>,
+    mask: usize,
+    enqueue_pos: AtomicUsize,
+    dequeue_pos: AtomicUsize,
+}
+
+unsafe impl<T: Send> Send for ArrayQueue<T> {}
+unsafe impl<T: Send> Sync for ArrayQueue<T> {}
+
+impl<T> ArrayQueue<T> {
+    // 용량은 2의 거듭제곱으로 올림한다.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be positive");
+        let capacity = capacity.next_power_of_two();
+        let buffer = (0..capacity)
+            .map(|i| Slot {
+                seq: AtomicUsize::new(i),
+                data: UnsafeCell::new(MaybeUninit::uninit()),
+            })
+            .collect();
+        ArrayQueue {
+            buffer,
+            mask: capacity - 1,
+            enqueue_pos: AtomicUsize::new(0),
+            dequeue_pos: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    // 가득 차 있다면 data를 돌려준다.
+    pub fn push(&self, data: T) -> Result<(), T> {
+        let mut pos = self.enqueue_pos.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.buffer[pos & self.mask];
+            let seq = slot.seq.load(Ordering::Acquire);
+            let diff = seq.wrapping_sub(pos) as isize;
+
+            if diff == 0 {
+                match self.enqueue_pos.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        unsafe { (*slot.data.get()).write(data) };
+                        slot.seq.store(pos.wrapping_add(1), Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(current) => pos = current,
+                }
+            } else if diff < 0 {
+                // 한 바퀴 앞의 값이 아직 pop되지 않았다.
+                // pop이 위치를 확보하고 아직 꺼내는 중이라면 가득 찬 것이 아니므로 기다린다.
+                fence(Ordering::SeqCst);
+                let dequeue_pos = self.dequeue_pos.load(Ordering::Relaxed);
+                if dequeue_pos.wrapping_add(self.capacity()) == pos {
+                    return Err(data);
+                }
+                hint::spin_loop();
+                pos = self.enqueue_pos.load(Ordering::Relaxed);
+            } else {
+                pos = self.enqueue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let mut pos = self.dequeue_pos.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.buffer[pos & self.mask];
+            let seq = slot.seq.load(Ordering::Acquire);
+            let diff = seq.wrapping_sub(pos.wrapping_add(1)) as isize;
+
+            if diff == 0 {
+                match self.dequeue_pos.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        let data = unsafe { (*slot.data.get()).assume_init_read() };
+                        slot.seq
+                            .store(pos.wrapping_add(self.capacity()), Ordering::Release);
+                        return Some(data);
+                    }
+                    Err(current) => pos = current,
+                }
+            } else if diff < 0 {
+                // 이 슬롯에 아직 값이 쓰이지 않았다.
+                // push가 위치를 확보하고 아직 쓰는 중이라면 비어 있는 것이 아니므로 기다린다.
+                // 그렇지 않으면 뒤의 push가 먼저 완료된 경우에 비어 있다고 잘못 반환하게 된다.
+                fence(Ordering::SeqCst);
+                let enqueue_pos = self.enqueue_pos.load(Ordering::Relaxed);
+                if enqueue_pos == pos {
+                    return None;
+                }
+                hint::spin_loop();
+                pos = self.dequeue_pos.load(Ordering::Relaxed);
+            } else {
+                pos = self.dequeue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        loop {
+            let enqueue_pos = self.enqueue_pos.load(Ordering::SeqCst);
+            let dequeue_pos = self.dequeue_pos.load(Ordering::SeqCst);
+            // 두 값을 읽는 사이에 enqueue_pos가 바뀌지 않았다면 일관된 값이다.
+            if self.enqueue_pos.load(Ordering::SeqCst) == enqueue_pos {
+                return enqueue_pos.wrapping_sub(dequeue_pos);
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Drop for ArrayQueue<T> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
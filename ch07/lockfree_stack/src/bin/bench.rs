@@ -0,0 +1,181 @@
+use std::{
+    sync::{Arc, Barrier},
+    thread,
+    time::{Duration, Instant},
+};
+
+use clap::{Arg, Command};
+use lockfree_stack::{
+    tagged::{Head, PackedHead},
+    EliminationStack, Stack, StackBad, TaggedStack,
+};
+
+struct CommandArgs {
+    stacks: Vec<String>,
+    num_threads: Vec<usize>,
+    num_op: usize,
+}
+
+fn get_command_args() -> CommandArgs {
+    let matches = Command::new("bench")
+        .arg(
+            Arg::new("stack")
+                .short('s')
+                .long("stack")
+                .value_name("STACK")
+                .num_args(1)
+                .help("Stack to measure (bad, epoch, tagged, dwcas, llsc, elim or all)")
+                .default_value("all"),
+        )
+        .arg(
+            Arg::new("num_thread")
+                .short('n')
+                .long("num_thread")
+                .value_name("NUM_THREAD")
+                .num_args(1..)
+                .value_delimiter(',')
+                .help("Numbers of threads to run (comma separated)")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("1,2,4,8,16,32,64"),
+        )
+        .arg(
+            Arg::new("num_op")
+                .short('o')
+                .long("num_op")
+                .value_name("NUM_OP")
+                .num_args(1)
+                .help("Number of push and pop pairs per thread")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("100000"),
+        )
+        .get_matches();
+
+    let stack = matches.get_one::<String>("stack").unwrap();
+    let stacks = match stack.as_str() {
+        "all" => ["bad", "epoch", "tagged", "dwcas", "llsc", "elim"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+        s => vec![s.to_string()],
+    };
+
+    CommandArgs {
+        stacks,
+        num_threads: matches.get_many("num_thread").unwrap().cloned().collect(),
+        num_op: matches.get_one("num_op").cloned().unwrap(),
+    }
+}
+
+trait BenchStack: Send + Sync + 'static {
+    fn push(&self, v: usize);
+    fn pop(&self) -> Option<usize>;
+}
+
+impl BenchStack for StackBad<usize> {
+    fn push(&self, v: usize) {
+        StackBad::push(self, v)
+    }
+
+    fn pop(&self) -> Option<usize> {
+        StackBad::pop(self)
+    }
+}
+
+impl BenchStack for Stack<usize> {
+    fn push(&self, v: usize) {
+        Stack::push(self, v)
+    }
+
+    fn pop(&self) -> Option<usize> {
+        Stack::pop(self)
+    }
+}
+
+impl BenchStack for EliminationStack<usize> {
+    fn push(&self, v: usize) {
+        EliminationStack::push(self, v)
+    }
+
+    fn pop(&self) -> Option<usize> {
+        EliminationStack::pop(self)
+    }
+}
+
+impl<H: Head + 'static> BenchStack for TaggedStack<usize, H> {
+    fn push(&self, v: usize) {
+        TaggedStack::push(self, v)
+    }
+
+    fn pop(&self) -> Option<usize> {
+        TaggedStack::pop(self)
+    }
+}
+
+// 각 스레드가 push와 pop을 번갈아 실행하는 데 걸린 시간
+fn bench<S: BenchStack>(stack: S, num_thread: usize, num_op: usize) -> Duration {
+    let stack = Arc::new(stack);
+    let barrier = Arc::new(Barrier::new(num_thread));
+    let threads = (0..num_thread)
+        .map(|_| {
+            let stack = stack.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                barrier.wait();
+                let start = Instant::now();
+                for n in 0..num_op {
+                    stack.push(n);
+                    stack.pop();
+                }
+                (start, Instant::now())
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // 코어가 적으면 메인 스레드가 깨어나기 전에 끝나는 스레드가 있으므로
+    // 각 스레드에서 잰 시각 중 가장 이른 시작부터 가장 늦은 종료까지를 측정한다.
+    let (start, end) = threads
+        .into_iter()
+        .map(|t| t.join().unwrap())
+        .reduce(|(s1, e1), (s2, e2)| (s1.min(s2), e1.max(e2)))
+        .unwrap();
+    end - start
+}
+
+fn main() {
+    let args = get_command_args();
+    println!("ops = {} per thread", args.num_op);
+    for &num_thread in args.num_threads.iter() {
+        println!("threads = {}", num_thread);
+        for name in args.stacks.iter() {
+            let num_op = args.num_op;
+            let elapsed = match name.as_str() {
+                // 해제된 노드를 읽을 수 있으므로 비교용으로만 사용한다.
+                "bad" => bench(StackBad::new(), num_thread, num_op),
+                "epoch" => bench(Stack::new(), num_thread, num_op),
+                "tagged" => bench(TaggedStack::<usize, PackedHead>::new(), num_thread, num_op),
+                #[cfg(target_arch = "x86_64")]
+                "dwcas" => bench(
+                    lockfree_stack::tagged::DwcasStack::new(),
+                    num_thread,
+                    num_op,
+                ),
+                #[cfg(target_arch = "aarch64")]
+                "llsc" => bench(lockfree_stack::tagged::LlscStack::new(), num_thread, num_op),
+                #[allow(unreachable_patterns)]
+                "dwcas" | "llsc" => {
+                    println!("{:>8}: not supported on this architecture", name);
+                    continue;
+                }
+                "elim" => bench(EliminationStack::new(), num_thread, num_op),
+                s => panic!("unknown stack: {}", s),
+            };
+            let total = num_thread * num_op * 2;
+            println!(
+                "{:>8}: {:>12.0} ops/s, elapsed = {:?}",
+                name,
+                total as f64 / elapsed.as_secs_f64(),
+                elapsed
+            );
+        }
+    }
+}
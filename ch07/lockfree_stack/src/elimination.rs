@@ -0,0 +1,150 @@
+use std::{
+    hint, ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+    thread,
+};
+
+use crate::{
+    epoch,
+    stack::{Node, Stack},
+    util::random,
+};
+
+// 제안한 push가 상대를 기다리는 횟수
+const PUSH_SPIN: usize = 128;
+// pop이 제안을 찾는 횟수
+const POP_SPIN: usize = 64;
+
+// pop이 제안을 가져간 것을 나타낸다. 할당된 노드의 주소와 겹치지 않는다.
+fn taken<T>() -> *mut Node<T> {
+    ptr::dangling_mut()
+}
+
+// 교환용 슬롯
+// null이면 비어 있고, 노드라면 push가 제안 중이며, taken이라면 pop이 가져갔다.
+#[repr(align(64))]
+struct Slot<T> {
+    offer: AtomicPtr<Node<T>>,
+}
+
+// 제거(elimination) 배열을 가지는 스택
+// 중앙 스택의 CAS가 경합으로 실패하면 임의의 슬롯에서 반대 연산을 기다린다.
+// 동시에 실행된 push와 pop은 중앙 스택을 거치지 않고 값을 직접 주고받으며
+// push 직후에 pop이 실행된 것으로 선형화된다.
+pub struct EliminationStack<T> {
+    stack: Stack<T>,
+    slots: Box<[Slot<T>]>,
+}
+
+unsafe impl<T: Send> Send for EliminationStack<T> {}
+unsafe impl<T: Send> Sync for EliminationStack<T> {}
+
+impl<T> EliminationStack<T> {
+    // 슬롯의 수는 CPU 수로 한다.
+    pub fn new() -> Self {
+        let n = thread::available_parallelism().map_or(1, |n| n.get());
+        Self::with_slots(n)
+    }
+
+    pub fn with_slots(num_slot: usize) -> Self {
+        assert!(num_slot > 0, "num_slot must be positive");
+        EliminationStack {
+            stack: Stack::new(),
+            slots: (0..num_slot)
+                .map(|_| Slot {
+                    offer: AtomicPtr::default(),
+                })
+                .collect(),
+        }
+    }
+
+    fn slot(&self) -> &Slot<T> {
+        &self.slots[random() as usize % self.slots.len()]
+    }
+
+    pub fn push(&self, data: T) {
+        let node = Node::new(data);
+        loop {
+            if self.stack.try_push(node) || self.try_eliminate_push(node) {
+                return;
+            }
+        }
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let guard = epoch::pin();
+        loop {
+            if let Ok(data) = self.stack.try_pop(&guard) {
+                return data;
+            }
+            if let Some(data) = self.try_eliminate_pop() {
+                return Some(data);
+            }
+        }
+    }
+
+    // 슬롯에 node를 제안하고 pop이 가져가기를 기다린다.
+    fn try_eliminate_push(&self, node: *mut Node<T>) -> bool {
+        let slot = self.slot();
+        if slot
+            .offer
+            .compare_exchange(ptr::null_mut(), node, Ordering::Release, Ordering::Relaxed)
+            .is_err()
+        {
+            return false;
+        }
+
+        for _ in 0..PUSH_SPIN {
+            if slot.offer.load(Ordering::Acquire) == taken() {
+                slot.offer.store(ptr::null_mut(), Ordering::Release);
+                return true;
+            }
+            hint::spin_loop();
+        }
+
+        // 제안을 철회한다. 실패했다면 그 사이에 pop이 가져갔다.
+        match slot.offer.compare_exchange(
+            node,
+            ptr::null_mut(),
+            Ordering::Relaxed,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => false,
+            Err(_) => {
+                slot.offer.store(ptr::null_mut(), Ordering::Release);
+                true
+            }
+        }
+    }
+
+    // 슬롯에서 push의 제안을 찾아 가져간다.
+    fn try_eliminate_pop(&self) -> Option<T> {
+        let slot = self.slot();
+        for _ in 0..POP_SPIN {
+            let node = slot.offer.load(Ordering::Relaxed);
+            // CAS가 성공했다면 제안된 노드의 소유권을 얻는다.
+            // 노드는 중앙 스택에 들어간 적이 없으므로 바로 해제해도 된다.
+            if !node.is_null()
+                && node != taken()
+                && slot
+                    .offer
+                    .compare_exchange(node, taken(), Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            {
+                return Some(unsafe { Node::into_data(node) });
+            }
+            hint::spin_loop();
+        }
+        None
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+}
+
+impl<T> Default for EliminationStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,283 @@
+// 에포크 기반 메모리 회수 (Epoch-Based Reclamation)
+//
+// 공유 포인터를 읽는 스레드는 pin으로 현재 에포크를 공개하고
+// 리스트에서 떼어 낸 노드는 바로 해제하지 않고 그 시점의 에포크와 함께 보관한다.
+// 전역 에포크는 pin된 모든 스레드가 현재 에포크를 관측한 경우에만 진행하므로
+// 에포크가 2 이상 진행한 후에는 떼어 낸 노드를 참조하는 스레드가 남아 있지 않다.
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    marker::PhantomData,
+    mem, ptr,
+    sync::{
+        atomic::{fence, AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+// 이 횟수만큼 pin할 때마다 회수를 시도한다.
+const COLLECT_INTERVAL: usize = 128;
+// 스레드가 보관하는 해제 대기 노드가 이 수를 넘으면 회수를 시도한다.
+const MAX_GARBAGE: usize = 256;
+
+// 해제를 미룬 객체
+struct Garbage {
+    ptr: *mut u8,
+    destroy: unsafe fn(*mut u8),
+}
+
+// 떼어 낸 객체는 어느 스레드에서 해제해도 된다.
+unsafe impl Send for Garbage {}
+
+impl Garbage {
+    fn new<T>(ptr: *mut T) -> Self {
+        unsafe fn destroy<T>(ptr: *mut u8) {
+            drop(Box::from_raw(ptr as *mut T));
+        }
+        Garbage {
+            ptr: ptr as *mut u8,
+            destroy: destroy::<T>,
+        }
+    }
+
+    fn destroy(self) {
+        unsafe { (self.destroy)(self.ptr) }
+    }
+}
+
+// 스레드별 에포크
+// state는 (에포크 << 1) | pin되어 있다면 1
+struct Participant {
+    state: AtomicUsize,
+    // 스레드가 사용 중이라면 true
+    // 종료한 스레드의 Participant는 다른 스레드가 재사용한다.
+    in_use: AtomicBool,
+    next: *const Participant,
+}
+
+unsafe impl Sync for Participant {}
+
+struct Global {
+    epoch: AtomicUsize,
+    // 추가만 하는 Participant의 리스트
+    participants: AtomicPtr<Participant>,
+    // 종료한 스레드가 남긴 해제 대기 객체
+    orphans: Mutex<Vec<(usize, Garbage)>>,
+}
+
+static GLOBAL: Global = Global {
+    epoch: AtomicUsize::new(0),
+    participants: AtomicPtr::new(ptr::null_mut()),
+    orphans: Mutex::new(Vec::new()),
+};
+
+impl Global {
+    fn register(&self) -> &'static Participant {
+        // 종료한 스레드의 것이 있다면 재사용
+        let mut p = self.participants.load(Ordering::Acquire) as *const Participant;
+        while let Some(participant) = unsafe { p.as_ref() } {
+            if participant
+                .in_use
+                .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+            {
+                return participant;
+            }
+            p = participant.next;
+        }
+
+        let participant = Box::into_raw(Box::new(Participant {
+            state: AtomicUsize::new(0),
+            in_use: AtomicBool::new(true),
+            next: ptr::null(),
+        }));
+        loop {
+            let head = self.participants.load(Ordering::Relaxed);
+            unsafe { (*participant).next = head };
+            if self
+                .participants
+                .compare_exchange_weak(head, participant, Ordering::Release, Ordering::Relaxed)
+                .is_ok()
+            {
+                return unsafe { &*participant };
+            }
+        }
+    }
+
+    // pin된 모든 스레드가 현재 에포크에 있다면 에포크를 진행한다.
+    // 진행 후의 에포크를 반환한다.
+    fn try_advance(&self) -> usize {
+        let epoch = self.epoch.load(Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+
+        let mut p = self.participants.load(Ordering::Acquire) as *const Participant;
+        while let Some(participant) = unsafe { p.as_ref() } {
+            let state = participant.state.load(Ordering::Relaxed);
+            if state & 1 == 1 && state >> 1 != epoch {
+                return epoch;
+            }
+            p = participant.next;
+        }
+        fence(Ordering::Acquire);
+
+        match self
+            .epoch
+            .compare_exchange(epoch, epoch + 1, Ordering::Release, Ordering::Relaxed)
+        {
+            Ok(_) => epoch + 1,
+            Err(current) => current,
+        }
+    }
+}
+
+// 에포크가 2 이상 진행한 객체를 꺼낸다.
+// 여러 스레드의 객체가 섞여 있으므로 모두 확인한다.
+fn take_expired(bag: &mut Vec<(usize, Garbage)>, epoch: usize) -> Vec<Garbage> {
+    let mut expired = Vec::new();
+    let mut i = 0;
+    while i < bag.len() {
+        if bag[i].0 + 2 <= epoch {
+            expired.push(bag.swap_remove(i).1);
+        } else {
+            i += 1;
+        }
+    }
+    expired
+}
+
+struct Local {
+    participant: &'static Participant,
+    // 중첩된 Guard의 수
+    guard_count: Cell<usize>,
+    pin_count: Cell<usize>,
+    // (떼어 낸 시점의 에포크, 객체)
+    // 에포크의 순서로 추가되므로 앞에서부터 해제할 수 있다.
+    garbage: RefCell<VecDeque<(usize, Garbage)>>,
+}
+
+impl Local {
+    fn new() -> Self {
+        Local {
+            participant: GLOBAL.register(),
+            guard_count: Cell::new(0),
+            pin_count: Cell::new(0),
+            garbage: RefCell::new(VecDeque::new()),
+        }
+    }
+
+    fn pin(&self) {
+        let count = self.guard_count.get();
+        self.guard_count.set(count + 1);
+        if count > 0 {
+            return;
+        }
+
+        let epoch = GLOBAL.epoch.load(Ordering::Relaxed);
+        self.participant
+            .state
+            .store(epoch << 1 | 1, Ordering::Relaxed);
+        // 에포크를 공개한 후에 공유 포인터를 읽도록 한다.
+        fence(Ordering::SeqCst);
+
+        let pins = self.pin_count.get() + 1;
+        self.pin_count.set(pins);
+        if pins.is_multiple_of(COLLECT_INTERVAL) {
+            self.collect();
+        }
+    }
+
+    fn unpin(&self) {
+        let count = self.guard_count.get() - 1;
+        self.guard_count.set(count);
+        if count == 0 {
+            self.participant.state.store(0, Ordering::Release);
+        }
+    }
+
+    fn defer(&self, garbage: Garbage) {
+        // 떼어 낸 후의 에포크를 기록한다.
+        fence(Ordering::SeqCst);
+        let epoch = GLOBAL.epoch.load(Ordering::Relaxed);
+        let len = {
+            let mut bag = self.garbage.borrow_mut();
+            bag.push_back((epoch, garbage));
+            bag.len()
+        };
+        if len > MAX_GARBAGE {
+            self.collect();
+        }
+    }
+
+    fn collect(&self) {
+        let epoch = GLOBAL.try_advance();
+
+        // 소멸자 안에서 다시 pin될 수 있으므로 빌린 상태로 해제하지 않는다.
+        let mut expired = Vec::new();
+        {
+            let mut bag = self.garbage.borrow_mut();
+            while bag.front().is_some_and(|(e, _)| e + 2 <= epoch) {
+                expired.push(bag.pop_front().unwrap().1);
+            }
+        }
+        expired.into_iter().for_each(Garbage::destroy);
+
+        let orphans = match GLOBAL.orphans.try_lock() {
+            Ok(mut orphans) if !orphans.is_empty() => take_expired(&mut orphans, epoch),
+            _ => return,
+        };
+        orphans.into_iter().for_each(Garbage::destroy);
+    }
+}
+
+impl Drop for Local {
+    fn drop(&mut self) {
+        // 남은 객체는 다른 스레드가 해제한다.
+        let bag = mem::take(self.garbage.get_mut());
+        if !bag.is_empty() {
+            GLOBAL.orphans.lock().unwrap().extend(bag);
+        }
+        self.participant.state.store(0, Ordering::Release);
+        self.participant.in_use.store(false, Ordering::Release);
+    }
+}
+
+thread_local! {
+    static LOCAL: Local = Local::new();
+}
+
+// 현재 스레드를 pin한다.
+// Guard가 살아 있는 동안 읽은 공유 포인터는 해제되지 않는다.
+pub fn pin() -> Guard {
+    LOCAL.with(Local::pin);
+    Guard {
+        _marker: PhantomData,
+    }
+}
+
+// pin된 상태를 나타내는 가드
+// 스레드에 묶여 있으므로 Send가 아니다.
+pub struct Guard {
+    _marker: PhantomData<*mut ()>,
+}
+
+impl Guard {
+    /// Box로 할당된 객체의 해제를 미룬다.
+    ///
+    /// # Safety
+    /// ptr은 Box::into_raw로 얻은 것이고 공유 위치에서 이미 떼어 내어져 있어야 한다.
+    /// 새로 읽는 스레드가 없고 이 함수는 같은 포인터에 대해 한 번만 호출해야 한다.
+    pub unsafe fn defer_destroy<T>(&self, ptr: *mut T) {
+        LOCAL.with(|local| local.defer(Garbage::new(ptr)));
+    }
+
+    // 에포크의 진행과 회수를 바로 시도한다.
+    pub fn flush(&self) {
+        LOCAL.with(Local::collect);
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        LOCAL.with(Local::unpin);
+    }
+}
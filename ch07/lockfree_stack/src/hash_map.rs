@@ -0,0 +1,318 @@
+// 분할 순서(split-ordered) 해시 맵
+//
+// 모든 요소를 하나의 정렬된 lock-free 리스트에 두고 버킷은 리스트 안의 더미 노드를 가리킨다.
+// 해시값의 비트를 뒤집은 값으로 정렬하므로 버킷 수를 2배로 늘려도 노드를 옮길 필요가 없고
+// 새 버킷은 부모 버킷의 구간을 나누는 위치에 더미 노드를 삽입하는 것만으로 초기화된다.
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hash},
+    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
+};
+
+use crate::{
+    epoch::{self, Guard},
+    util::{is_marked, marked, unmarked},
+};
+
+// 세그먼트당 버킷 수
+const SEGMENT_SIZE: usize = 1024;
+// 세그먼트 수. 버킷 수의 상한은 SEGMENT_SIZE * NUM_SEGMENT
+const NUM_SEGMENT: usize = 1024;
+const MAX_BUCKETS: usize = SEGMENT_SIZE * NUM_SEGMENT;
+// 버킷당 평균 요소 수가 이 값을 넘으면 버킷 수를 2배로 한다.
+const LOAD_FACTOR: usize = 2;
+
+struct Node<K, V> {
+    // 정렬 키. 요소는 최하위 비트가 1이고 더미 노드는 0이다.
+    so_key: u64,
+    // 더미 노드는 None
+    entry: Option<(K, V)>,
+    next: AtomicPtr<Node<K, V>>,
+}
+
+type Segment<K, V> = [AtomicPtr<Node<K, V>>; SEGMENT_SIZE];
+
+fn regular_key(hash: u64) -> u64 {
+    (hash | 1 << 63).reverse_bits()
+}
+
+fn dummy_key(bucket: usize) -> u64 {
+    (bucket as u64).reverse_bits()
+}
+
+// 최상위 비트를 지운 버킷
+fn parent(bucket: usize) -> usize {
+    bucket & !(1 << (usize::BITS - 1 - bucket.leading_zeros()))
+}
+
+pub struct HashMap<K, V> {
+    // 버킷이 가리키는 더미 노드. 세그먼트는 필요할 때 할당한다.
+    segments: Box<[AtomicPtr<Segment<K, V>>]>,
+    // 사용 중인 버킷 수 (2의 거듭제곱)
+    size: AtomicUsize,
+    count: AtomicUsize,
+    hasher: RandomState,
+}
+
+unsafe impl<K: Send + Sync, V: Send + Sync> Send for HashMap<K, V> {}
+unsafe impl<K: Send + Sync, V: Send + Sync> Sync for HashMap<K, V> {}
+
+impl<K: Hash + Eq, V: Clone> HashMap<K, V> {
+    pub fn new() -> Self {
+        let map = HashMap {
+            segments: (0..NUM_SEGMENT).map(|_| AtomicPtr::default()).collect(),
+            size: AtomicUsize::new(2),
+            count: AtomicUsize::new(0),
+            hasher: RandomState::new(),
+        };
+        // 버킷 0의 더미 노드가 리스트의 선두가 된다.
+        let head = Box::into_raw(Box::new(Node {
+            so_key: dummy_key(0),
+            entry: None,
+            next: AtomicPtr::default(),
+        }));
+        map.bucket_slot(0).store(head, Ordering::Release);
+        map
+    }
+
+    fn bucket_slot(&self, bucket: usize) -> &AtomicPtr<Node<K, V>> {
+        let segment = &self.segments[bucket / SEGMENT_SIZE];
+        let mut p = segment.load(Ordering::Acquire);
+        if p.is_null() {
+            let new = Box::into_raw(Box::new(std::array::from_fn(|_| AtomicPtr::default())));
+            match segment.compare_exchange(p, new, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => p = new,
+                Err(current) => {
+                    drop(unsafe { Box::from_raw(new) });
+                    p = current;
+                }
+            }
+        }
+        unsafe { &(*p)[bucket % SEGMENT_SIZE] }
+    }
+
+    // 버킷의 더미 노드를 반환한다. 초기화되지 않았다면 부모 버킷부터 초기화한다.
+    fn bucket(&self, bucket: usize, guard: &Guard) -> *mut Node<K, V> {
+        let slot = self.bucket_slot(bucket);
+        let dummy = slot.load(Ordering::Acquire);
+        if !dummy.is_null() {
+            return dummy;
+        }
+
+        let start = self.bucket(parent(bucket), guard);
+        let node = Box::into_raw(Box::new(Node {
+            so_key: dummy_key(bucket),
+            entry: None,
+            next: AtomicPtr::default(),
+        }));
+        // 다른 스레드가 먼저 삽입했다면 그 더미 노드를 사용한다.
+        let dummy = match unsafe { self.insert_node(start, node, None, guard) } {
+            Ok(()) => node,
+            Err(existing) => {
+                drop(unsafe { Box::from_raw(node) });
+                existing
+            }
+        };
+        slot.store(dummy, Ordering::Release);
+        dummy
+    }
+
+    // start 이후에서 (so_key, key)의 위치를 찾는다.
+    // 반환값은 (앞 노드의 next, 찾은 노드 또는 다음 노드, 찾았는지)
+    // 도중에 논리 삭제된 노드를 발견하면 리스트에서 떼어 내고 해제를 미룬다.
+    unsafe fn find(
+        &self,
+        start: *mut Node<K, V>,
+        so_key: u64,
+        key: Option<&K>,
+        guard: &Guard,
+    ) -> (*const AtomicPtr<Node<K, V>>, *mut Node<K, V>, bool) {
+        'retry: loop {
+            // 더미 노드는 삭제되지 않는다.
+            let mut prev: *const AtomicPtr<Node<K, V>> = &(*start).next;
+            let mut curr = (*prev).load(Ordering::Acquire);
+            loop {
+                if curr.is_null() {
+                    return (prev, curr, false);
+                }
+
+                let next = (*curr).next.load(Ordering::Acquire);
+                if is_marked(next) {
+                    let next = unmarked(next);
+                    if (*prev)
+                        .compare_exchange(curr, next, Ordering::AcqRel, Ordering::Acquire)
+                        .is_err()
+                    {
+                        continue 'retry;
+                    }
+                    // 떼어 낸 스레드만 해제를 미룬다.
+                    guard.defer_destroy(curr);
+                    curr = next;
+                    continue;
+                }
+
+                if (*curr).so_key > so_key {
+                    return (prev, curr, false);
+                }
+                if (*curr).so_key == so_key {
+                    let found = match (key, &(*curr).entry) {
+                        (Some(key), Some((k, _))) => k == key,
+                        // 더미 노드의 so_key는 버킷마다 다르다.
+                        _ => true,
+                    };
+                    if found {
+                        return (prev, curr, true);
+                    }
+                }
+                prev = &(*curr).next;
+                curr = next;
+            }
+        }
+    }
+
+    // 같은 키가 있다면 삽입하지 않고 그 노드를 반환한다.
+    unsafe fn insert_node(
+        &self,
+        start: *mut Node<K, V>,
+        node: *mut Node<K, V>,
+        key: Option<&K>,
+        guard: &Guard,
+    ) -> Result<(), *mut Node<K, V>> {
+        loop {
+            let (prev, curr, found) = self.find(start, (*node).so_key, key, guard);
+            if found {
+                return Err(curr);
+            }
+            (*node).next.store(curr, Ordering::Relaxed);
+            if (*prev)
+                .compare_exchange(curr, node, Ordering::Release, Ordering::Relaxed)
+                .is_ok()
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    fn locate(&self, key: &K, guard: &Guard) -> (*mut Node<K, V>, u64) {
+        let hash = self.hasher.hash_one(key);
+        let size = self.size.load(Ordering::Acquire);
+        let start = self.bucket(hash as usize & (size - 1), guard);
+        (start, regular_key(hash))
+    }
+
+    // 키가 이미 있다면 값을 바꾸지 않고 false를 반환한다.
+    pub fn insert(&self, key: K, value: V) -> bool {
+        let guard = epoch::pin();
+        let (start, so_key) = self.locate(&key, &guard);
+        let node = Box::into_raw(Box::new(Node {
+            so_key,
+            entry: Some((key, value)),
+            next: AtomicPtr::default(),
+        }));
+
+        let key = unsafe { (*node).entry.as_ref().map(|(k, _)| k) };
+        if unsafe { self.insert_node(start, node, key, &guard) }.is_err() {
+            drop(unsafe { Box::from_raw(node) });
+            return false;
+        }
+
+        let count = self.count.fetch_add(1, Ordering::Relaxed) + 1;
+        let size = self.size.load(Ordering::Relaxed);
+        if count > size * LOAD_FACTOR && size * 2 <= MAX_BUCKETS {
+            // 실패했다면 다른 스레드가 늘렸다.
+            let _ =
+                self.size
+                    .compare_exchange(size, size * 2, Ordering::Release, Ordering::Relaxed);
+        }
+        true
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        let guard = epoch::pin();
+        let (start, so_key) = self.locate(key, &guard);
+        let (_, curr, found) = unsafe { self.find(start, so_key, Some(key), &guard) };
+        if !found {
+            return None;
+        }
+        unsafe { (*curr).entry.as_ref().map(|(_, v)| v.clone()) }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        let guard = epoch::pin();
+        let (start, so_key) = self.locate(key, &guard);
+        unsafe { self.find(start, so_key, Some(key), &guard).2 }
+    }
+
+    // 값은 다른 스레드가 get으로 읽고 있을 수 있으므로 복제해서 반환한다.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let guard = epoch::pin();
+        let (start, so_key) = self.locate(key, &guard);
+        loop {
+            let (prev, curr, found) = unsafe { self.find(start, so_key, Some(key), &guard) };
+            if !found {
+                return None;
+            }
+
+            // next에 표시를 한 스레드가 삭제한 것이 된다.
+            let next = unsafe { (*curr).next.load(Ordering::Acquire) };
+            if is_marked(next)
+                || unsafe {
+                    (*curr)
+                        .next
+                        .compare_exchange(next, marked(next), Ordering::AcqRel, Ordering::Relaxed)
+                        .is_err()
+                }
+            {
+                continue;
+            }
+
+            let value = unsafe { (*curr).entry.as_ref().map(|(_, v)| v.clone()) };
+            self.count.fetch_sub(1, Ordering::Relaxed);
+
+            // 떼어 내지 못했다면 find가 떼어 낸다.
+            unsafe {
+                if (*prev)
+                    .compare_exchange(curr, next, Ordering::AcqRel, Ordering::Relaxed)
+                    .is_ok()
+                {
+                    guard.defer_destroy(curr);
+                } else {
+                    self.find(start, so_key, Some(key), &guard);
+                }
+            }
+            return value;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Default for HashMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Drop for HashMap<K, V> {
+    fn drop(&mut self) {
+        // 모든 노드는 버킷 0의 더미 노드부터 이어져 있다.
+        let segment = *self.segments[0].get_mut();
+        let mut node = unsafe { *(*segment)[0].get_mut() };
+        while !node.is_null() {
+            let n = unsafe { Box::from_raw(node) };
+            node = unmarked(n.next.load(Ordering::Relaxed));
+        }
+        for segment in self.segments.iter_mut() {
+            let p = *segment.get_mut();
+            if !p.is_null() {
+                drop(unsafe { Box::from_raw(p) });
+            }
+        }
+    }
+}
@@ -3,12 +3,36 @@ use std::{
     sync::atomic::{self, AtomicPtr},
 };
 
+mod array_queue;
+mod elimination;
+pub mod epoch;
+mod hash_map;
+mod queue;
+mod skip_list;
+mod stack;
+// 포인터의 상위 비트에 태그를 넣으므로 64비트 환경에서만 사용할 수 있다.
+#[cfg(target_pointer_width = "64")]
+pub mod tagged;
+mod util;
+
+pub use array_queue::ArrayQueue;
+pub use elimination::EliminationStack;
+pub use hash_map::HashMap;
+pub use queue::Queue;
+pub use skip_list::SkipList;
+pub use stack::Stack;
+#[cfg(target_pointer_width = "64")]
+pub use tagged::TaggedStack;
+
 // 스택의 노드
 struct Node<T> {
     next: AtomicPtr<Node<T>>,
     data: T,
 }
 
+// pop한 노드를 바로 해제하므로 다른 스레드가 읽고 있는 노드를 해제하거나
+// 해제 후에 같은 주소로 할당된 노드에 의해 CAS가 잘못 성공하는 ABA가 발생한다.
+// 비교를 위해 남겨 둔다. 올바른 구현은 Stack을 사용한다.
 pub struct StackBad<T> {
     head: AtomicPtr<Node<T>>,
 }
@@ -52,6 +76,14 @@ impl<T> StackBad<T> {
     }
 
     pub fn pop(&self) -> Option<T> {
+        self.pop_with(|| ())
+    }
+
+    // 검사용. 처음 시도할 때 head와 next를 읽은 후 CAS하기 전에 f를 호출한다.
+    // f 안에서 다른 스레드가 스택을 조작하면 ABA를 재현할 수 있다.
+    #[doc(hidden)]
+    pub fn pop_with(&self, f: impl FnOnce()) -> Option<T> {
+        let mut f = Some(f);
         unsafe {
             loop {
                 let head = self.head.load(atomic::Ordering::Relaxed);
@@ -62,6 +94,10 @@ impl<T> StackBad<T> {
 
                 let next = (*head).next.load(atomic::Ordering::Relaxed);
 
+                if let Some(f) = f.take() {
+                    f();
+                }
+
                 if self
                     .head
                     .compare_exchange_weak(
@@ -73,11 +109,23 @@ impl<T> StackBad<T> {
                     .is_ok()
                 {
                     let h = Box::from_raw(head);
-                    return Some((*h).data);
+                    return Some(h.data);
                 }
             }
         }
     }
+
+    // 검사용. 선두 노드의 주소
+    #[doc(hidden)]
+    pub fn head_addr(&self) -> usize {
+        self.head.load(atomic::Ordering::Relaxed) as usize
+    }
+}
+
+impl<T> Default for StackBad<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<T> Drop for StackBad<T> {
@@ -0,0 +1,683 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::{BTreeMap, HashSet},
+    env, hint,
+    process::{self, Command},
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Barrier,
+    },
+    thread,
+};
+
+use lockfree_stack::{
+    tagged::{Head, PackedHead},
+    ArrayQueue, EliminationStack, HashMap, Queue, SkipList, Stack, StackBad, TaggedStack,
+};
+
+// Miri는 느리므로 횟수를 줄인다.
+const NUM_LOOP: usize = if cfg!(miri) { 200 } else { 200_000 };
+const NUM_THREAD: usize = if cfg!(miri) { 4 } else { 8 };
+const BATCH: usize = 4;
+
+// 해제한 메모리를 0xdd로 덮어쓰는 할당자
+// 해제된 노드를 읽으면 next나 data가 깨진 값이 되므로 use-after-free를 검출하기 쉬워진다.
+// Miri에서 실행하면(cargo +nightly miri run) StackBad의 use-after-free를 직접 보고한다.
+struct PoisonAlloc;
+
+unsafe impl GlobalAlloc for PoisonAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        std::ptr::write_bytes(ptr, 0xdd, layout.size());
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOC: PoisonAlloc = PoisonAlloc;
+
+trait ConcurrentStack: Send + Sync + 'static {
+    fn push(&self, v: u64);
+    fn pop(&self) -> Option<u64>;
+}
+
+impl ConcurrentStack for Stack<u64> {
+    fn push(&self, v: u64) {
+        Stack::push(self, v)
+    }
+
+    fn pop(&self) -> Option<u64> {
+        Stack::pop(self)
+    }
+}
+
+impl<H: Head + 'static> ConcurrentStack for TaggedStack<u64, H> {
+    fn push(&self, v: u64) {
+        TaggedStack::push(self, v)
+    }
+
+    fn pop(&self) -> Option<u64> {
+        TaggedStack::pop(self)
+    }
+}
+
+impl ConcurrentStack for EliminationStack<u64> {
+    fn push(&self, v: u64) {
+        EliminationStack::push(self, v)
+    }
+
+    fn pop(&self) -> Option<u64> {
+        EliminationStack::pop(self)
+    }
+}
+
+// ABA를 재현하기 위한 검사용 조작
+trait AbaStack: ConcurrentStack {
+    fn pop_with(&self, f: impl FnOnce()) -> Option<u64>;
+    fn head_addr(&self) -> usize;
+}
+
+impl AbaStack for Stack<u64> {
+    fn pop_with(&self, f: impl FnOnce()) -> Option<u64> {
+        Stack::pop_with(self, f)
+    }
+
+    fn head_addr(&self) -> usize {
+        Stack::head_addr(self)
+    }
+}
+
+impl ConcurrentStack for StackBad<u64> {
+    fn push(&self, v: u64) {
+        StackBad::push(self, v)
+    }
+
+    fn pop(&self) -> Option<u64> {
+        StackBad::pop(self)
+    }
+}
+
+impl AbaStack for StackBad<u64> {
+    fn pop_with(&self, f: impl FnOnce()) -> Option<u64> {
+        StackBad::pop_with(self, f)
+    }
+
+    fn head_addr(&self) -> usize {
+        StackBad::head_addr(self)
+    }
+}
+
+// 해제된 주소가 재사용되기를 기다리는 최대 할당 횟수
+const MAX_REUSE: usize = 64;
+
+// ABA를 일으키는 순서로 실행한다.
+// 1. A가 head(X)와 next(Y)를 읽고 멈춘다.
+// 2. B가 X와 Y를 pop하고, X의 주소에 할당된 노드를 push한다.
+// 3. A가 CAS한다. X가 해제되지 않았다면 head가 바뀌었으므로 실패하고 다시 시도한다.
+// 스택의 내용은 [3, 2, 1]에서 시작하고, A는 4를 pop하고 1이 남아야 한다.
+// 할당자는 스레드별로 해제된 메모리를 재사용하므로 노드는 모두 B가 할당한다.
+fn check_aba<S: AbaStack>(stack: S) -> Result<(), String> {
+    let barrier = Barrier::new(2);
+    // X와 같은 크기의 메모리를 잡아 두어서 X 이외의 해제된 주소가 push에 사용되지 않게 한다.
+    let mut holders: Vec<Box<[usize; 2]>> = Vec::with_capacity(MAX_REUSE);
+    let popped = thread::scope(|s| {
+        s.spawn(|| {
+            for v in [1, 2, 3] {
+                stack.push(v);
+            }
+            let x = stack.head_addr();
+            barrier.wait();
+
+            // A가 읽을 때까지 기다린다.
+            barrier.wait();
+            stack.pop();
+            stack.pop();
+            stack.push(4);
+            for _ in 0..MAX_REUSE {
+                if stack.head_addr() == x {
+                    break;
+                }
+                stack.pop();
+                // 0으로 초기화하는 할당은 calloc이 되어 해제된 메모리를 재사용하지 않을 수 있다.
+                holders.push(Box::new(hint::black_box([0; 2])));
+                stack.push(4);
+            }
+            barrier.wait();
+        });
+
+        barrier.wait();
+        stack.pop_with(|| {
+            barrier.wait();
+            // B가 끝날 때까지 기다린다.
+            barrier.wait();
+        })
+    });
+    let mut rest = Vec::new();
+    while let Some(v) = stack.pop() {
+        rest.push(v);
+    }
+    // 잘못된 CAS가 성공한 경우 해제된 Y를 holders와 스택이 함께 가리키게 되므로 해제하지 않는다.
+    std::mem::forget(holders);
+
+    if popped != Some(4) || rest != [1] {
+        return Err(format!(
+            "popped {:?} and {:?} remained, expected Some(4) and [1]",
+            popped, rest
+        ));
+    }
+    Ok(())
+}
+
+// 각 스레드가 push와 pop을 반복하고 push한 값이 정확히 한 번씩 pop되었는지 확인한다.
+// 값의 상위 비트는 스레드 번호, 하위 비트는 일련번호
+fn stress<S: ConcurrentStack>(stack: S) -> Result<(), String> {
+    let stack = Arc::new(stack);
+    let barrier = Arc::new(Barrier::new(NUM_THREAD));
+    let mut popped = (0..NUM_THREAD)
+        .map(|i| {
+            let stack = stack.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let mut popped = Vec::with_capacity(NUM_LOOP);
+                barrier.wait();
+                // 해제 직후의 주소가 다음 push에서 재사용되도록 여러 개씩 push와 pop을 반복한다.
+                for base in (0..NUM_LOOP).step_by(BATCH) {
+                    let end = (base + BATCH).min(NUM_LOOP);
+                    for n in base..end {
+                        stack.push(((i as u64) << 32) | n as u64);
+                    }
+                    for _ in base..end {
+                        if let Some(v) = stack.pop() {
+                            popped.push(v);
+                        }
+                    }
+                }
+                popped
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .flat_map(|t| t.join().unwrap())
+        .collect::<Vec<_>>();
+    while let Some(v) = stack.pop() {
+        popped.push(v);
+    }
+
+    let mut seen = HashSet::with_capacity(popped.len());
+    for &v in popped.iter() {
+        let (i, n) = ((v >> 32) as usize, (v & 0xffff_ffff) as usize);
+        if i >= NUM_THREAD || n >= NUM_LOOP {
+            return Err(format!("corrupted value: {:#x}", v));
+        }
+        if !seen.insert(v) {
+            return Err(format!("popped twice: {:#x}", v));
+        }
+    }
+    if seen.len() != NUM_LOOP * NUM_THREAD {
+        return Err(format!(
+            "lost {} values",
+            NUM_LOOP * NUM_THREAD - seen.len()
+        ));
+    }
+
+    Ok(())
+}
+
+// 실패하면 종료 코드 1로 종료한다.
+fn report(name: &str, result: Result<(), String>) {
+    match result {
+        Ok(()) => println!("{}: OK ({} values)", name, NUM_LOOP * NUM_THREAD),
+        Err(err) => {
+            println!("{}: FAILED, {}", name, err);
+            process::exit(1);
+        }
+    }
+}
+
+fn report_aba(name: &str, result: Result<(), String>) {
+    match result {
+        Ok(()) => println!("{}: ABA OK", name),
+        Err(err) => {
+            println!("{}: ABA FAILED, {}", name, err);
+            process::exit(1);
+        }
+    }
+}
+
+const NUM_PRODUCER: usize = 4;
+const NUM_CONSUMER: usize = 4;
+const NUM_VALUE: usize = if cfg!(miri) { 100 } else { 100_000 };
+const ARRAY_QUEUE_CAPACITY: usize = 64;
+
+trait ConcurrentQueue: Send + Sync + 'static {
+    // 가득 차 있다면 false
+    fn push(&self, v: u64) -> bool;
+    fn pop(&self) -> Option<u64>;
+}
+
+impl ConcurrentQueue for Queue<u64> {
+    fn push(&self, v: u64) -> bool {
+        Queue::push(self, v);
+        true
+    }
+
+    fn pop(&self) -> Option<u64> {
+        Queue::pop(self)
+    }
+}
+
+impl ConcurrentQueue for ArrayQueue<u64> {
+    fn push(&self, v: u64) -> bool {
+        ArrayQueue::push(self, v).is_ok()
+    }
+
+    fn pop(&self) -> Option<u64> {
+        ArrayQueue::pop(self)
+    }
+}
+
+// 작업의 호출 전과 응답 후의 시각
+// 실제 작업은 이 구간 안의 한 시점에 일어난 것으로 보여야 한다.
+#[derive(Clone, Copy)]
+struct Interval {
+    start: u64,
+    end: u64,
+}
+
+static CLOCK: AtomicU64 = AtomicU64::new(0);
+
+fn timed<R>(f: impl FnOnce() -> R) -> (R, Interval) {
+    let start = CLOCK.fetch_add(1, Ordering::SeqCst);
+    let r = f();
+    let end = CLOCK.fetch_add(1, Ordering::SeqCst);
+    (r, Interval { start, end })
+}
+
+// (enqueue 구간, dequeue 구간)
+type History = Vec<(u64, Interval, Interval)>;
+
+// 선형화 가능성의 검사
+// 여러 생산자와 소비자가 기록한 이력이 FIFO 큐의 어떤 순차 실행과도 모순되지 않는지 확인한다.
+//
+// - 모든 값이 정확히 한 번씩 pop된다.
+// - enqueue(a)가 enqueue(b)의 호출 전에 완료되었다면 dequeue(b)가 dequeue(a)의 호출 전에 완료되지 않는다.
+// - a가 enqueue된 후 dequeue되기 전에 호출과 응답이 모두 있는 pop은 None을 반환하지 않는다.
+fn check_queue<Q: ConcurrentQueue>(queue: Q) -> Result<(), String> {
+    let queue = Arc::new(queue);
+    let total = NUM_PRODUCER * NUM_VALUE;
+    let popped = Arc::new(AtomicUsize::new(0));
+    let barrier = Arc::new(Barrier::new(NUM_PRODUCER + NUM_CONSUMER));
+
+    let producers = (0..NUM_PRODUCER)
+        .map(|i| {
+            let queue = queue.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let mut pushed = Vec::with_capacity(NUM_VALUE);
+                barrier.wait();
+                for n in 0..NUM_VALUE {
+                    let v = ((i as u64) << 32) | n as u64;
+                    loop {
+                        // 가득 차서 실패한 push는 큐를 바꾸지 않으므로 기록하지 않는다.
+                        let (ok, interval) = timed(|| queue.push(v));
+                        if ok {
+                            pushed.push((v, interval));
+                            break;
+                        }
+                        thread::yield_now();
+                    }
+                }
+                pushed
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let consumers = (0..NUM_CONSUMER)
+        .map(|_| {
+            let queue = queue.clone();
+            let popped = popped.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let mut values = Vec::new();
+                let mut empties = Vec::new();
+                barrier.wait();
+                while popped.load(Ordering::Relaxed) < total {
+                    match timed(|| queue.pop()) {
+                        (Some(v), interval) => {
+                            values.push((v, interval));
+                            popped.fetch_add(1, Ordering::Relaxed);
+                        }
+                        (None, interval) => {
+                            empties.push(interval);
+                            thread::yield_now();
+                        }
+                    }
+                }
+                (values, empties)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut enqueued = vec![None; total];
+    for (v, interval) in producers.into_iter().flat_map(|t| t.join().unwrap()) {
+        let (i, n) = ((v >> 32) as usize, (v & 0xffff_ffff) as usize);
+        enqueued[i * NUM_VALUE + n] = Some(interval);
+    }
+
+    let mut history: History = Vec::with_capacity(total);
+    let mut empties = Vec::new();
+    let mut seen = vec![false; total];
+    for t in consumers {
+        let (values, e) = t.join().unwrap();
+        empties.extend(e);
+        for (v, deq) in values {
+            let (i, n) = ((v >> 32) as usize, (v & 0xffff_ffff) as usize);
+            if i >= NUM_PRODUCER || n >= NUM_VALUE {
+                return Err(format!("corrupted value: {:#x}", v));
+            }
+            let idx = i * NUM_VALUE + n;
+            if seen[idx] {
+                return Err(format!("popped twice: {:#x}", v));
+            }
+            seen[idx] = true;
+            history.push((v, enqueued[idx].unwrap(), deq));
+        }
+    }
+    if history.len() != total {
+        return Err(format!("lost {} values", total - history.len()));
+    }
+
+    // enqueue의 완료 시각 순으로 훑으면서 그때까지 완료된 enqueue 중 가장 늦게 시작한 dequeue를 유지한다.
+    history.sort_by_key(|(_, enq, _)| enq.end);
+
+    let mut by_enq_start = history.clone();
+    by_enq_start.sort_by_key(|(_, enq, _)| enq.start);
+    let mut i = 0;
+    let mut latest: Option<(u64, Interval)> = None;
+    for &(b, enq_b, deq_b) in by_enq_start.iter() {
+        while i < history.len() && history[i].1.end < enq_b.start {
+            let (a, _, deq_a) = history[i];
+            if latest.is_none_or(|(_, d)| d.start < deq_a.start) {
+                latest = Some((a, deq_a));
+            }
+            i += 1;
+        }
+        if let Some((a, deq_a)) = latest {
+            if deq_b.end < deq_a.start {
+                return Err(format!("{:#x} overtook {:#x}", b, a));
+            }
+        }
+    }
+
+    empties.sort_by_key(|e| e.start);
+    let mut i = 0;
+    let mut latest: Option<(u64, Interval)> = None;
+    for e in empties.iter() {
+        while i < history.len() && history[i].1.end < e.start {
+            let (a, _, deq_a) = history[i];
+            if latest.is_none_or(|(_, d)| d.start < deq_a.start) {
+                latest = Some((a, deq_a));
+            }
+            i += 1;
+        }
+        if let Some((a, deq_a)) = latest {
+            if e.end < deq_a.start {
+                return Err(format!("pop returned None while {:#x} was queued", a));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn report_queue(name: &str, result: Result<(), String>) {
+    match result {
+        Ok(()) => println!(
+            "{}: OK ({} values, linearizable)",
+            name,
+            NUM_PRODUCER * NUM_VALUE
+        ),
+        Err(err) => {
+            println!("{}: FAILED, {}", name, err);
+            process::exit(1);
+        }
+    }
+}
+
+const NUM_MAP_OP: usize = if cfg!(miri) { 100 } else { 100_000 };
+const KEY_RANGE: u64 = 1024;
+
+trait ConcurrentMap: Send + Sync + 'static {
+    fn insert(&self, k: u64, v: u64) -> bool;
+    fn remove(&self, k: &u64) -> Option<u64>;
+    fn get(&self, k: &u64) -> Option<u64>;
+    fn len(&self) -> usize;
+    // 키의 순서로 반환한다. 순서가 없는 맵은 None
+    fn range(&self, from: u64, to: u64) -> Option<Vec<(u64, u64)>>;
+}
+
+impl ConcurrentMap for HashMap<u64, u64> {
+    fn insert(&self, k: u64, v: u64) -> bool {
+        HashMap::insert(self, k, v)
+    }
+
+    fn remove(&self, k: &u64) -> Option<u64> {
+        HashMap::remove(self, k)
+    }
+
+    fn get(&self, k: &u64) -> Option<u64> {
+        HashMap::get(self, k)
+    }
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+
+    fn range(&self, _from: u64, _to: u64) -> Option<Vec<(u64, u64)>> {
+        None
+    }
+}
+
+impl ConcurrentMap for SkipList<u64, u64> {
+    fn insert(&self, k: u64, v: u64) -> bool {
+        SkipList::insert(self, k, v)
+    }
+
+    fn remove(&self, k: &u64) -> Option<u64> {
+        SkipList::remove(self, k)
+    }
+
+    fn get(&self, k: &u64) -> Option<u64> {
+        SkipList::get(self, k)
+    }
+
+    fn len(&self) -> usize {
+        SkipList::len(self)
+    }
+
+    fn range(&self, from: u64, to: u64) -> Option<Vec<(u64, u64)>> {
+        Some(SkipList::range(self, from..to))
+    }
+}
+
+fn xorshift(x: &mut u64) -> u64 {
+    *x ^= *x << 13;
+    *x ^= *x >> 7;
+    *x ^= *x << 17;
+    *x
+}
+
+// 단일 스레드에서 BTreeMap과 같은 결과를 반환하는지 확인한다.
+fn check_map_sequential<M: ConcurrentMap>(map: &M) -> Result<(), String> {
+    let mut model = BTreeMap::new();
+    let mut x = 0x1234_5678_9abc_def0;
+    for i in 0..NUM_MAP_OP as u64 {
+        let k = xorshift(&mut x) % KEY_RANGE;
+        let (op, expected, actual) = match xorshift(&mut x) % 4 {
+            0 | 1 => {
+                let inserted = !model.contains_key(&k);
+                model.entry(k).or_insert(i);
+                ("insert", inserted.to_string(), map.insert(k, i).to_string())
+            }
+            2 => (
+                "remove",
+                format!("{:?}", model.remove(&k)),
+                format!("{:?}", map.remove(&k)),
+            ),
+            _ => (
+                "get",
+                format!("{:?}", model.get(&k)),
+                format!("{:?}", map.get(&k)),
+            ),
+        };
+        if expected != actual {
+            return Err(format!(
+                "{}({}) returned {}, expected {}",
+                op, k, actual, expected
+            ));
+        }
+
+        if i % 1000 == 0 {
+            let (from, to) = (k, k + KEY_RANGE / 8);
+            let expected: Vec<_> = model.range(from..to).map(|(&k, &v)| (k, v)).collect();
+            if map.range(from, to).is_some_and(|r| r != expected) {
+                return Err(format!("range({}..{}) is inconsistent", from, to));
+            }
+        }
+    }
+    if map.len() != model.len() {
+        return Err(format!("len is {}, expected {}", map.len(), model.len()));
+    }
+    Ok(())
+}
+
+// 여러 스레드에서 좁은 범위의 키를 삽입, 삭제, 검색한다.
+// 키마다 성공한 삽입의 수에서 성공한 삭제의 수를 뺀 값이 최종 상태와 일치하는지 확인한다.
+// 값의 상위 비트는 키이므로 다른 키의 값을 반환하면 검출된다.
+fn check_map_concurrent<M: ConcurrentMap>(map: M) -> Result<(), String> {
+    let map = Arc::new(map);
+    let barrier = Arc::new(Barrier::new(NUM_THREAD));
+    let results = (0..NUM_THREAD)
+        .map(|i| {
+            let map = map.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || -> Result<Vec<i64>, String> {
+                let mut balance = vec![0_i64; KEY_RANGE as usize];
+                let mut x = (i as u64 + 1) * 0x9e37_79b9_7f4a_7c15;
+                barrier.wait();
+                for n in 0..NUM_MAP_OP as u64 {
+                    let k = xorshift(&mut x) % KEY_RANGE;
+                    let returned = match xorshift(&mut x) % 3 {
+                        0 => {
+                            if map.insert(k, (k << 32) | n) {
+                                balance[k as usize] += 1;
+                            }
+                            None
+                        }
+                        1 => map.remove(&k).inspect(|_| balance[k as usize] -= 1),
+                        _ => map.get(&k),
+                    };
+                    if let Some(v) = returned {
+                        if v >> 32 != k {
+                            return Err(format!("key {} has value {:#x}", k, v));
+                        }
+                    }
+                }
+                Ok(balance)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut balance = vec![0_i64; KEY_RANGE as usize];
+    for t in results {
+        for (total, b) in balance.iter_mut().zip(t.join().unwrap()?) {
+            *total += b;
+        }
+    }
+
+    let mut present = Vec::new();
+    for (k, &b) in balance.iter().enumerate() {
+        let k = k as u64;
+        let exists = map.get(&k).is_some();
+        if !(b == 0 && !exists || b == 1 && exists) {
+            return Err(format!("key {}: balance {}, exists {}", k, b, exists));
+        }
+        if exists {
+            present.push(k);
+        }
+    }
+    if map.len() != present.len() {
+        return Err(format!("len is {}, expected {}", map.len(), present.len()));
+    }
+    if let Some(all) = map.range(0, KEY_RANGE) {
+        if all.iter().map(|&(k, _)| k).collect::<Vec<_>>() != present {
+            return Err("range is inconsistent".to_string());
+        }
+    }
+    Ok(())
+}
+
+fn report_map<M: ConcurrentMap>(name: &str, new: impl Fn() -> M) {
+    match check_map_sequential(&new()).and_then(|()| check_map_concurrent(new())) {
+        Ok(()) => println!(
+            "{}: OK ({} ops sequential, {} ops concurrent)",
+            name,
+            NUM_MAP_OP,
+            NUM_MAP_OP * NUM_THREAD
+        ),
+        Err(err) => {
+            println!("{}: FAILED, {}", name, err);
+            process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    match env::args().nth(1).as_deref() {
+        // 자식 프로세스로 실행된다.
+        Some("bad") => report_aba("StackBad", check_aba(StackBad::new())),
+        _ => {
+            report_aba("Stack", check_aba(Stack::new()));
+            report("Stack", stress(Stack::new()));
+            report("TaggedStack", stress(TaggedStack::<u64, PackedHead>::new()));
+            #[cfg(target_arch = "x86_64")]
+            report(
+                "DwcasStack",
+                stress(lockfree_stack::tagged::DwcasStack::new()),
+            );
+            #[cfg(target_arch = "aarch64")]
+            report(
+                "LlscStack",
+                stress(lockfree_stack::tagged::LlscStack::new()),
+            );
+            report("EliminationStack", stress(EliminationStack::new()));
+
+            report_queue("Queue", check_queue(Queue::new()));
+            report_queue(
+                "ArrayQueue",
+                check_queue(ArrayQueue::new(ARRAY_QUEUE_CAPACITY)),
+            );
+
+            report_map("HashMap", HashMap::new);
+            report_map("SkipList", SkipList::new);
+
+            // StackBad는 해제된 노드를 읽으므로 별도의 프로세스에서 실행하고
+            // ABA로 실패했는지 확인한다.
+            let exe = env::current_exe().unwrap();
+            let output = Command::new(exe).arg("bad").output().unwrap();
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            print!("{}", stdout);
+            if output.status.success() || !stdout.contains("StackBad: ABA FAILED") {
+                println!(
+                    "StackBad: ABA not detected, process terminated ({})",
+                    output.status
+                );
+                process::exit(1);
+            }
+            println!("StackBad: ABA detected as expected");
+        }
+    }
+}
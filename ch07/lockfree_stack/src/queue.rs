@@ -0,0 +1,134 @@
+use std::{
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use crate::epoch;
+
+struct Node<T> {
+    next: AtomicPtr<Node<T>>,
+    // 선두의 더미 노드는 값을 가지지 않는다.
+    data: MaybeUninit<T>,
+}
+
+impl<T> Node<T> {
+    fn new(data: MaybeUninit<T>) -> *mut Node<T> {
+        Box::into_raw(Box::new(Node {
+            next: AtomicPtr::new(ptr::null_mut()),
+            data,
+        }))
+    }
+}
+
+// Michael-Scott 큐
+// head는 항상 더미 노드를 가리키고 pop은 head를 다음 노드로 옮긴 후 그 노드의 값을 꺼낸다.
+// tail은 마지막 노드나 그 하나 앞을 가리키며, 뒤처진 tail은 발견한 스레드가 옮긴다.
+// 떼어 낸 더미 노드는 에포크 기반 회수로 해제한다.
+pub struct Queue<T> {
+    head: AtomicPtr<Node<T>>,
+    tail: AtomicPtr<Node<T>>,
+    _marker: PhantomData<T>,
+}
+
+unsafe impl<T: Send> Sync for Queue<T> {}
+
+impl<T> Queue<T> {
+    pub fn new() -> Self {
+        let dummy = Node::new(MaybeUninit::uninit());
+        Queue {
+            head: AtomicPtr::new(dummy),
+            tail: AtomicPtr::new(dummy),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn push(&self, data: T) {
+        let node = Node::new(MaybeUninit::new(data));
+        // tail이 가리키는 노드는 다른 스레드가 떼어 낼 수 있으므로 pin한다.
+        let _guard = epoch::pin();
+        loop {
+            let tail = self.tail.load(Ordering::Acquire);
+            let next = unsafe { (*tail).next.load(Ordering::Acquire) };
+
+            if !next.is_null() {
+                // tail이 뒤처져 있으므로 옮긴 후에 다시 시도한다.
+                let _ =
+                    self.tail
+                        .compare_exchange(tail, next, Ordering::Release, Ordering::Relaxed);
+                continue;
+            }
+
+            if unsafe {
+                (*tail)
+                    .next
+                    .compare_exchange(next, node, Ordering::Release, Ordering::Relaxed)
+                    .is_ok()
+            } {
+                // 실패해도 다른 스레드가 옮긴다.
+                let _ =
+                    self.tail
+                        .compare_exchange(tail, node, Ordering::Release, Ordering::Relaxed);
+                return;
+            }
+        }
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let guard = epoch::pin();
+        loop {
+            let head = self.head.load(Ordering::Acquire);
+            let next = unsafe { (*head).next.load(Ordering::Acquire) };
+            if next.is_null() {
+                return None;
+            }
+
+            // tail이 떼어 낼 더미 노드에 남지 않도록 먼저 옮긴다.
+            let tail = self.tail.load(Ordering::Relaxed);
+            if tail == head {
+                let _ =
+                    self.tail
+                        .compare_exchange(tail, next, Ordering::Release, Ordering::Relaxed);
+            }
+
+            if self
+                .head
+                .compare_exchange(head, next, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+            {
+                // next가 새로운 더미 노드가 되고 값은 head를 옮긴 스레드가 꺼낸다.
+                unsafe {
+                    let data = (*next).data.assume_init_read();
+                    guard.defer_destroy(head);
+                    return Some(data);
+                }
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        let _guard = epoch::pin();
+        let head = self.head.load(Ordering::Acquire);
+        unsafe { (*head).next.load(Ordering::Acquire).is_null() }
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for Queue<T> {
+    fn drop(&mut self) {
+        // 더미 노드의 값은 이미 꺼냈거나 초기화되지 않았다.
+        let dummy = unsafe { Box::from_raw(*self.head.get_mut()) };
+        let mut node = dummy.next.load(Ordering::Relaxed);
+        while !node.is_null() {
+            let mut n = unsafe { Box::from_raw(node) };
+            unsafe { n.data.assume_init_drop() };
+            node = *n.next.get_mut();
+        }
+    }
+}
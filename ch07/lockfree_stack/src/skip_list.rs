@@ -0,0 +1,305 @@
+// lock-free 스킵 리스트
+//
+// 각 층은 Harris의 리스트와 같이 next에 표시를 해서 노드를 논리 삭제한다.
+// 최하층의 next에 표시를 한 스레드가 삭제한 것이 되며, 위의 층은 키를 찾을 때 지나가는 스레드가 떼어 낸다.
+// 삽입 중인 노드는 삭제된 후에도 위의 층에 연결될 수 있으므로
+// 삽입한 스레드와 삭제한 스레드가 모두 떼어 내기를 마친 후에 해제를 미룬다.
+use std::{
+    ops::{Bound, RangeBounds},
+    ptr,
+    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
+};
+
+use crate::{
+    epoch::{self, Guard},
+    util::{is_marked, mark, random, unmarked},
+};
+
+const MAX_HEIGHT: usize = 16;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    // 삽입 중과 삭제 전을 나타내는 참조 수. 0이 된 시점에 해제를 미룬다.
+    refs: AtomicUsize,
+    next: Box<[AtomicPtr<Node<K, V>>]>,
+}
+
+impl<K, V> Node<K, V> {
+    fn height(&self) -> usize {
+        self.next.len()
+    }
+}
+
+// 높이는 1/2의 확률로 1씩 늘어난다.
+fn random_height() -> usize {
+    (random().trailing_zeros() as usize + 1).min(MAX_HEIGHT)
+}
+
+type Links<K, V> = [AtomicPtr<Node<K, V>>];
+
+pub struct SkipList<K, V> {
+    head: [AtomicPtr<Node<K, V>>; MAX_HEIGHT],
+    len: AtomicUsize,
+}
+
+unsafe impl<K: Send + Sync, V: Send + Sync> Send for SkipList<K, V> {}
+unsafe impl<K: Send + Sync, V: Send + Sync> Sync for SkipList<K, V> {}
+
+// 각 층에서 key 바로 앞의 링크와 key 이상인 첫 노드
+struct Position<K, V> {
+    preds: [*const AtomicPtr<Node<K, V>>; MAX_HEIGHT],
+    succs: [*mut Node<K, V>; MAX_HEIGHT],
+}
+
+impl<K: Ord, V: Clone> SkipList<K, V> {
+    pub fn new() -> Self {
+        SkipList {
+            head: Default::default(),
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    // 도중에 논리 삭제된 노드를 발견하면 그 층에서 떼어 낸다.
+    unsafe fn find(&self, key: &K) -> (Position<K, V>, bool) {
+        'retry: loop {
+            let mut pos = Position {
+                preds: [ptr::null(); MAX_HEIGHT],
+                succs: [ptr::null_mut(); MAX_HEIGHT],
+            };
+            let mut pred: &Links<K, V> = &self.head;
+            for level in (0..MAX_HEIGHT).rev() {
+                let mut curr = pred[level].load(Ordering::Acquire);
+                if is_marked(curr) {
+                    // pred가 이 층에서 삭제되었다.
+                    continue 'retry;
+                }
+                while !curr.is_null() {
+                    let succ = (*curr).next[level].load(Ordering::Acquire);
+                    if is_marked(succ) {
+                        let succ = unmarked(succ);
+                        if pred[level]
+                            .compare_exchange(curr, succ, Ordering::AcqRel, Ordering::Acquire)
+                            .is_err()
+                        {
+                            continue 'retry;
+                        }
+                        curr = succ;
+                        continue;
+                    }
+                    if (*curr).key >= *key {
+                        break;
+                    }
+                    pred = &(*curr).next;
+                    curr = succ;
+                }
+                pos.preds[level] = &pred[level];
+                pos.succs[level] = curr;
+            }
+            let found = !pos.succs[0].is_null() && (*pos.succs[0]).key == *key;
+            return (pos, found);
+        }
+    }
+
+    // 참조 수를 줄이고 마지막이라면 해제를 미룬다.
+    // 호출 전에 find로 모든 층에서 떼어 내야 한다.
+    unsafe fn release(&self, node: *mut Node<K, V>, guard: &Guard) {
+        if (*node).refs.fetch_sub(1, Ordering::AcqRel) == 1 {
+            guard.defer_destroy(node);
+        }
+    }
+
+    // 키가 이미 있다면 값을 바꾸지 않고 false를 반환한다.
+    pub fn insert(&self, key: K, value: V) -> bool {
+        let guard = epoch::pin();
+        let height = random_height();
+        let node = Box::into_raw(Box::new(Node {
+            key,
+            value,
+            // 삽입한 스레드와 삭제할 스레드의 몫
+            refs: AtomicUsize::new(2),
+            next: (0..height).map(|_| AtomicPtr::default()).collect(),
+        }));
+
+        unsafe {
+            let key = &(*node).key;
+            let mut pos = loop {
+                let (pos, found) = self.find(key);
+                if found {
+                    drop(Box::from_raw(node));
+                    return false;
+                }
+                for level in 0..height {
+                    (*node).next[level].store(pos.succs[level], Ordering::Relaxed);
+                }
+                // 최하층에 연결된 시점에 삽입된 것이 된다.
+                if (*pos.preds[0])
+                    .compare_exchange(pos.succs[0], node, Ordering::Release, Ordering::Relaxed)
+                    .is_ok()
+                {
+                    break pos;
+                }
+            };
+            self.len.fetch_add(1, Ordering::Relaxed);
+
+            'link: for level in 1..height {
+                loop {
+                    let succ = pos.succs[level];
+                    // 삭제가 시작되었다면 위의 층에는 연결하지 않는다.
+                    let next = (*node).next[level].load(Ordering::Acquire);
+                    if is_marked(next)
+                        || (next != succ
+                            && (*node).next[level]
+                                .compare_exchange(next, succ, Ordering::AcqRel, Ordering::Acquire)
+                                .is_err())
+                    {
+                        break 'link;
+                    }
+                    if (*pos.preds[level])
+                        .compare_exchange(succ, node, Ordering::Release, Ordering::Relaxed)
+                        .is_ok()
+                    {
+                        break;
+                    }
+                    let (p, _) = self.find(key);
+                    if p.succs[0] != node {
+                        // 이미 삭제되었다.
+                        break 'link;
+                    }
+                    pos = p;
+                }
+            }
+
+            // 연결하는 동안 삭제되었다면 연결한 층에서 다시 떼어 낸다.
+            if is_marked((*node).next[0].load(Ordering::Acquire)) {
+                self.find(key);
+            }
+            self.release(node, &guard);
+        }
+        true
+    }
+
+    // 논리 삭제된 노드를 건너뛰면서 읽기만 한다.
+    unsafe fn search(&self, bound: Bound<&K>) -> *mut Node<K, V> {
+        let before = |k: &K| match bound {
+            Bound::Included(key) => k < key,
+            Bound::Excluded(key) => k <= key,
+            Bound::Unbounded => false,
+        };
+
+        let mut pred: &Links<K, V> = &self.head;
+        let mut curr = ptr::null_mut();
+        for level in (0..MAX_HEIGHT).rev() {
+            curr = unmarked(pred[level].load(Ordering::Acquire));
+            while !curr.is_null() {
+                let succ = (*curr).next[level].load(Ordering::Acquire);
+                if is_marked(succ) {
+                    curr = unmarked(succ);
+                    continue;
+                }
+                if !before(&(*curr).key) {
+                    break;
+                }
+                pred = &(*curr).next;
+                curr = succ;
+            }
+        }
+        curr
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        let _guard = epoch::pin();
+        unsafe {
+            let node = self.search(Bound::Included(key));
+            if node.is_null() || (*node).key != *key {
+                return None;
+            }
+            Some((*node).value.clone())
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    // 값은 다른 스레드가 읽고 있을 수 있으므로 복제해서 반환한다.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let guard = epoch::pin();
+        unsafe {
+            let (pos, found) = self.find(key);
+            if !found {
+                return None;
+            }
+            let node = pos.succs[0];
+
+            // 위의 층부터 표시하고 최하층에 표시한 스레드가 삭제한 것이 된다.
+            for level in (1..(*node).height()).rev() {
+                mark(&(*node).next[level]);
+            }
+            if !mark(&(*node).next[0]) {
+                return None;
+            }
+            self.len.fetch_sub(1, Ordering::Relaxed);
+
+            let value = (*node).value.clone();
+            self.find(key);
+            self.release(node, &guard);
+            Some(value)
+        }
+    }
+
+    // range에 있는 요소를 키의 순서로 복제해서 반환한다.
+    // 도중의 삽입과 삭제는 반영될 수도 있고 반영되지 않을 수도 있다.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Vec<(K, V)>
+    where
+        K: Clone,
+    {
+        let _guard = epoch::pin();
+        let mut result = Vec::new();
+        unsafe {
+            let mut node = self.search(range.start_bound());
+            while !node.is_null() {
+                let next = (*node).next[0].load(Ordering::Acquire);
+                let key = &(*node).key;
+                let in_range = match range.end_bound() {
+                    Bound::Included(end) => key <= end,
+                    Bound::Excluded(end) => key < end,
+                    Bound::Unbounded => true,
+                };
+                if !in_range {
+                    break;
+                }
+                if !is_marked(next) {
+                    result.push((key.clone(), (*node).value.clone()));
+                }
+                node = unmarked(next);
+            }
+        }
+        result
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K: Ord, V: Clone> Default for SkipList<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Drop for SkipList<K, V> {
+    fn drop(&mut self) {
+        // 해제를 미루지 않은 노드는 모두 최하층에 연결되어 있다.
+        let mut node = *self.head[0].get_mut();
+        while !node.is_null() {
+            let n = unsafe { Box::from_raw(node) };
+            node = unmarked(n.next[0].load(Ordering::Relaxed));
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::{
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use crate::epoch::{self, Guard};
+
+pub(crate) struct Node<T> {
+    next: AtomicPtr<Node<T>>,
+    // pop에서 꺼낸 후에 노드만 해제하므로 여기서는 drop하지 않는다.
+    data: ManuallyDrop<T>,
+}
+
+impl<T> Node<T> {
+    pub(crate) fn new(data: T) -> *mut Node<T> {
+        Box::into_raw(Box::new(Node {
+            next: AtomicPtr::new(ptr::null_mut()),
+            data: ManuallyDrop::new(data),
+        }))
+    }
+
+    // 스택에 넣은 적이 없는 노드에서 값을 꺼내고 해제한다.
+    pub(crate) unsafe fn into_data(node: *mut Node<T>) -> T {
+        ManuallyDrop::into_inner(Box::from_raw(node).data)
+    }
+}
+
+// 에포크 기반 회수를 사용하는 Treiber 스택
+// pop한 노드는 pin된 스레드가 없어질 때까지 해제되지 않으므로
+// 같은 주소가 재사용되어 CAS가 잘못 성공하는 ABA가 발생하지 않는다.
+pub struct Stack<T> {
+    head: AtomicPtr<Node<T>>,
+    _marker: PhantomData<T>,
+}
+
+unsafe impl<T: Send> Sync for Stack<T> {}
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Stack {
+            head: AtomicPtr::new(ptr::null_mut()),
+            _marker: PhantomData,
+        }
+    }
+
+    // push는 head를 역참조하지 않으므로 pin할 필요가 없다.
+    pub fn push(&self, data: T) {
+        let node = Node::new(data);
+        while !self.try_push(node) {}
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let guard = epoch::pin();
+        loop {
+            if let Ok(data) = self.try_pop(&guard) {
+                return data;
+            }
+        }
+    }
+
+    // CAS를 한 번만 시도한다. 경합으로 실패하면 false
+    pub(crate) fn try_push(&self, node: *mut Node<T>) -> bool {
+        let head = self.head.load(Ordering::Relaxed);
+        unsafe { (*node).next.store(head, Ordering::Relaxed) };
+        self.head
+            .compare_exchange(head, node, Ordering::Release, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    // 검사용. 처음 시도할 때 head와 next를 읽은 후 CAS하기 전에 f를 호출한다.
+    #[doc(hidden)]
+    pub fn pop_with(&self, f: impl FnOnce()) -> Option<T> {
+        let guard = epoch::pin();
+        let mut f = Some(f);
+        loop {
+            let between = || {
+                if let Some(f) = f.take() {
+                    f();
+                }
+            };
+            if let Ok(data) = self.try_pop_with(&guard, between) {
+                return data;
+            }
+        }
+    }
+
+    // CAS를 한 번만 시도한다. 경합으로 실패하면 Err
+    pub(crate) fn try_pop(&self, guard: &Guard) -> Result<Option<T>, ()> {
+        self.try_pop_with(guard, || ())
+    }
+
+    fn try_pop_with(&self, guard: &Guard, between: impl FnOnce()) -> Result<Option<T>, ()> {
+        let head = self.head.load(Ordering::Acquire);
+        if head.is_null() {
+            return Ok(None);
+        }
+
+        // pin되어 있으므로 다른 스레드가 pop한 노드라도 아직 해제되지 않았다.
+        let next = unsafe { (*head).next.load(Ordering::Relaxed) };
+        between();
+
+        if self
+            .head
+            .compare_exchange(head, next, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return Err(());
+        }
+        unsafe {
+            let data = ptr::read(&(*head).data);
+            guard.defer_destroy(head);
+            Ok(Some(ManuallyDrop::into_inner(data)))
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire).is_null()
+    }
+
+    // 검사용. 선두 노드의 주소
+    #[doc(hidden)]
+    pub fn head_addr(&self) -> usize {
+        self.head.load(Ordering::Relaxed) as usize
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for Stack<T> {
+    fn drop(&mut self) {
+        let mut node = *self.head.get_mut();
+        while !node.is_null() {
+            let mut n = unsafe { Box::from_raw(node) };
+            unsafe { ManuallyDrop::drop(&mut n.data) };
+            node = *n.next.get_mut();
+        }
+    }
+}
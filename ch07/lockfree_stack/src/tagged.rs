@@ -0,0 +1,352 @@
+// head에 버전 카운터(태그)를 함께 두어 ABA를 방지하는 스택
+//
+// pop은 head와 태그를 함께 CAS하고 태그를 1 늘리므로
+// 읽은 후에 같은 주소의 노드가 다시 push되어도 태그가 달라 CAS가 실패한다.
+// 실패할 CAS를 위해 이미 pop된 노드의 next를 읽을 수 있으므로
+// pop한 노드는 해제하지 않고 프리 리스트에 모아 두었다가 push에서 재사용한다.
+// 노드는 스택이 파기될 때 한꺼번에 해제한다.
+use std::{
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr,
+    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
+};
+
+#[cfg(target_arch = "x86_64")]
+use std::{arch::asm, sync::atomic::AtomicU64};
+
+// 노드의 링크 부분
+// 모든 노드의 선두에 두므로 head의 구현은 데이터의 타입을 알 필요가 없다.
+#[repr(C)]
+pub struct Link {
+    next: AtomicPtr<Link>,
+}
+
+// ABA를 방지하는 head의 구현
+pub trait Head: Default + Send + Sync {
+    /// node를 선두에 추가한다.
+    ///
+    /// # Safety
+    /// node는 이 head에 없는 유효한 노드여야 한다.
+    unsafe fn push(&self, node: *mut Link);
+
+    /// 선두의 노드를 떼어 낸다. 비어 있다면 null을 반환한다.
+    ///
+    /// # Safety
+    /// 한 번이라도 push된 노드는 head를 사용하는 동안 해제되면 안 된다.
+    unsafe fn pop(&self) -> *mut Link;
+
+    fn is_empty(&self) -> bool;
+}
+
+// 포인터의 사용하지 않는 상위 16비트에 태그를 넣는다.
+// x86-64와 AArch64의 유저 공간 주소는 48비트에 들어간다.
+// 태그는 65536번의 pop으로 한 바퀴 도므로 그동안 멈춰 있던 스레드에는 ABA가 발생할 수 있다.
+#[derive(Default)]
+pub struct PackedHead {
+    head: AtomicUsize,
+}
+
+const TAG_SHIFT: u32 = 48;
+const PTR_MASK: usize = (1 << TAG_SHIFT) - 1;
+
+impl PackedHead {
+    fn pack(ptr: *mut Link, tag: usize) -> usize {
+        debug_assert_eq!(ptr as usize & !PTR_MASK, 0, "address exceeds 48 bits");
+        ptr as usize | tag << TAG_SHIFT
+    }
+
+    fn unpack(head: usize) -> (*mut Link, usize) {
+        ((head & PTR_MASK) as *mut Link, head >> TAG_SHIFT)
+    }
+}
+
+impl Head for PackedHead {
+    unsafe fn push(&self, node: *mut Link) {
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            let (next, tag) = PackedHead::unpack(head);
+            (*node).next.store(next, Ordering::Relaxed);
+
+            let new = PackedHead::pack(node, tag.wrapping_add(1));
+            match self
+                .head
+                .compare_exchange_weak(head, new, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    unsafe fn pop(&self) -> *mut Link {
+        let mut head = self.head.load(Ordering::Acquire);
+        loop {
+            let (node, tag) = PackedHead::unpack(head);
+            if node.is_null() {
+                return ptr::null_mut();
+            }
+
+            // 다른 스레드가 이미 pop한 노드라도 해제되지 않으므로 읽을 수 있다.
+            // 그 경우에는 태그가 바뀌었으므로 아래의 CAS가 실패한다.
+            let next = (*node).next.load(Ordering::Relaxed);
+
+            let new = PackedHead::pack(next, tag.wrapping_add(1));
+            match self
+                .head
+                .compare_exchange_weak(head, new, Ordering::Acquire, Ordering::Acquire)
+            {
+                Ok(_) => return node,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire) & PTR_MASK == 0
+    }
+}
+
+// 포인터와 64비트 태그를 cmpxchg16b로 함께 CAS한다.
+// 태그가 한 바퀴 돌 일은 사실상 없다.
+#[cfg(target_arch = "x86_64")]
+#[repr(C, align(16))]
+pub struct DwcasHead {
+    ptr: AtomicU64,
+    tag: AtomicU64,
+}
+
+#[cfg(target_arch = "x86_64")]
+impl Default for DwcasHead {
+    fn default() -> Self {
+        assert!(
+            std::is_x86_feature_detected!("cmpxchg16b"),
+            "cmpxchg16b is not supported"
+        );
+        DwcasHead {
+            ptr: AtomicU64::new(0),
+            tag: AtomicU64::new(0),
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl DwcasHead {
+    // (ptr, tag)가 current와 같다면 new로 바꾼다.
+    // 실패하면 그 시점의 값을 반환한다.
+    fn cas(&self, current: (u64, u64), new: (u64, u64)) -> Result<(), (u64, u64)> {
+        let ok: u8;
+        let (ptr, tag): (u64, u64);
+        // rbx는 LLVM이 사용하므로 직접 지정할 수 없다. 다른 레지스터와 교환해서 사용한다.
+        unsafe {
+            asm!(
+                "xchg {rbx_tmp}, rbx",
+                "lock cmpxchg16b xmmword ptr [{dst}]",
+                "sete {ok}",
+                "mov rbx, {rbx_tmp}",
+                dst = in(reg) self as *const DwcasHead,
+                rbx_tmp = inout(reg) new.0 => _,
+                ok = out(reg_byte) ok,
+                in("rcx") new.1,
+                inout("rax") current.0 => ptr,
+                inout("rdx") current.1 => tag,
+                options(nostack),
+            );
+        }
+        if ok == 1 {
+            Ok(())
+        } else {
+            Err((ptr, tag))
+        }
+    }
+
+    // 태그와 포인터를 따로 읽으므로 서로 다른 시점의 값이 조합될 수 있지만
+    // CAS가 128비트 전체를 비교하므로 그 경우에는 CAS가 실패할 뿐이다.
+    fn load(&self) -> (u64, u64) {
+        let tag = self.tag.load(Ordering::Acquire);
+        let ptr = self.ptr.load(Ordering::Acquire);
+        (ptr, tag)
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl Head for DwcasHead {
+    unsafe fn push(&self, node: *mut Link) {
+        let mut head = self.load();
+        loop {
+            (*node).next.store(head.0 as *mut Link, Ordering::Relaxed);
+            match self.cas(head, (node as u64, head.1.wrapping_add(1))) {
+                Ok(()) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    unsafe fn pop(&self) -> *mut Link {
+        let mut head = self.load();
+        loop {
+            let node = head.0 as *mut Link;
+            if node.is_null() {
+                return ptr::null_mut();
+            }
+
+            let next = (*node).next.load(Ordering::Relaxed);
+            match self.cas(head, (next as u64, head.1.wrapping_add(1))) {
+                Ok(()) => return node,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.ptr.load(Ordering::Acquire) == 0
+    }
+}
+
+// LL/SC로 pop한다.
+// ldaxr 후에 head에 쓰기가 있었다면 같은 값으로 되돌아왔더라도 stlxr이 실패하므로 태그가 필요 없다.
+#[cfg(target_arch = "aarch64")]
+#[derive(Default)]
+pub struct LlscHead {
+    head: AtomicPtr<Link>,
+}
+
+#[cfg(target_arch = "aarch64")]
+impl Head for LlscHead {
+    // push는 새 노드의 next에 읽은 head를 넣을 뿐이므로 ABA의 영향을 받지 않는다.
+    unsafe fn push(&self, node: *mut Link) {
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            (*node).next.store(head, Ordering::Relaxed);
+            match self
+                .head
+                .compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    unsafe fn pop(&self) -> *mut Link {
+        let node: *mut Link;
+        // Link의 next는 오프셋 0에 있다.
+        // 비어 있다면 clrex로 배타 모니터를 해제하고 null을 반환한다.
+        std::arch::asm!(
+            "2:",
+            "ldaxr {node}, [{addr}]",
+            "cbz {node}, 3f",
+            "ldr {next}, [{node}]",
+            "stlxr {status:w}, {next}, [{addr}]",
+            "cbnz {status:w}, 2b",
+            "b 4f",
+            "3:",
+            "clrex",
+            "4:",
+            addr = in(reg) self.head.as_ptr(),
+            node = out(reg) node,
+            next = out(reg) _,
+            status = out(reg) _,
+            options(nostack),
+        );
+        node
+    }
+
+    fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire).is_null()
+    }
+}
+
+#[repr(C)]
+struct Node<T> {
+    link: Link,
+    data: MaybeUninit<T>,
+}
+
+// H로 head의 구현을 선택한다.
+pub struct TaggedStack<T, H: Head = PackedHead> {
+    head: H,
+    // pop한 노드를 재사용하기 위한 프리 리스트
+    free: H,
+    _marker: PhantomData<T>,
+}
+
+#[cfg(target_arch = "x86_64")]
+pub type DwcasStack<T> = TaggedStack<T, DwcasHead>;
+
+#[cfg(target_arch = "aarch64")]
+pub type LlscStack<T> = TaggedStack<T, LlscHead>;
+
+unsafe impl<T: Send, H: Head> Send for TaggedStack<T, H> {}
+unsafe impl<T: Send, H: Head> Sync for TaggedStack<T, H> {}
+
+impl<T, H: Head> TaggedStack<T, H> {
+    pub fn new() -> Self {
+        TaggedStack {
+            head: H::default(),
+            free: H::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn push(&self, data: T) {
+        let node = unsafe { self.free.pop() } as *mut Node<T>;
+        let node = if node.is_null() {
+            Box::into_raw(Box::new(Node {
+                link: Link {
+                    next: AtomicPtr::new(ptr::null_mut()),
+                },
+                data: MaybeUninit::new(data),
+            }))
+        } else {
+            // 다른 스레드는 link만 읽으므로 data에 써도 된다.
+            unsafe { (*node).data.write(data) };
+            node
+        };
+        unsafe { self.head.push(node as *mut Link) };
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let node = unsafe { self.head.pop() } as *mut Node<T>;
+        if node.is_null() {
+            return None;
+        }
+        unsafe {
+            let data = (*node).data.assume_init_read();
+            self.free.push(node as *mut Link);
+            Some(data)
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_empty()
+    }
+}
+
+impl<T, H: Head> Default for TaggedStack<T, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, H: Head> Drop for TaggedStack<T, H> {
+    fn drop(&mut self) {
+        unsafe {
+            loop {
+                let node = self.head.pop() as *mut Node<T>;
+                if node.is_null() {
+                    break;
+                }
+                let mut node = Box::from_raw(node);
+                node.data.assume_init_drop();
+            }
+            loop {
+                let node = self.free.pop() as *mut Node<T>;
+                if node.is_null() {
+                    break;
+                }
+                drop(Box::from_raw(node));
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::{
+    cell::Cell,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+thread_local! {
+    static RNG: Cell<u32> = const { Cell::new(0) };
+}
+
+// 스레드별 xorshift
+pub(crate) fn random() -> u32 {
+    RNG.with(|rng| {
+        let mut x = rng.get();
+        if x == 0 {
+            // 스레드마다 다른 값으로 시작한다.
+            x = (rng as *const Cell<u32> as usize >> 4) as u32 | 1;
+        }
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        rng.set(x);
+        x
+    })
+}
+
+// 노드의 정렬이 2 이상이므로 포인터의 최하위 비트를 논리 삭제 표시로 사용한다.
+// next에 표시가 있는 노드는 삭제된 노드이고 그 next는 더 이상 바뀌지 않는다.
+pub(crate) fn is_marked<T>(p: *mut T) -> bool {
+    p.addr() & 1 == 1
+}
+
+pub(crate) fn marked<T>(p: *mut T) -> *mut T {
+    p.map_addr(|a| a | 1)
+}
+
+pub(crate) fn unmarked<T>(p: *mut T) -> *mut T {
+    p.map_addr(|a| a & !1)
+}
+
+// link에 표시를 한다. 이 호출로 표시했다면 true
+pub(crate) fn mark<T>(link: &AtomicPtr<T>) -> bool {
+    let mut p = link.load(Ordering::Acquire);
+    loop {
+        if is_marked(p) {
+            return false;
+        }
+        match link.compare_exchange_weak(p, marked(p), Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => return true,
+            Err(current) => p = current,
+        }
+    }
+}
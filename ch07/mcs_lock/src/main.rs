@@ -6,21 +6,35 @@ const NUM_THREAD: usize = 4;
 fn main() {
     let n = Arc::new(mcs_lock::MCSLock::new(0));
     (0..NUM_THREAD)
-        .map(|_| {
+        .map(|i| {
             let n0 = n.clone();
             thread::spawn(move || {
-                let mut node = mcs_lock::MCSNode::new();
-                (0..NUM_LOOP).for_each(|_| {
-                    let mut r = n0.lock(&mut node);
-                    *r += 1;
-                });
+                if i == 0 {
+                    // 노드를 직접 준비하는 경우
+                    let mut node = mcs_lock::MCSNode::new();
+                    (0..NUM_LOOP).for_each(|_| {
+                        let mut r = n0.lock_with(&mut node);
+                        *r += 1;
+                    });
+                } else {
+                    (0..NUM_LOOP).for_each(|_| {
+                        let mut r = n0.lock();
+                        *r += 1;
+                    });
+                }
             })
         })
         .collect::<Vec<_>>()
         .into_iter()
         .for_each(|t| t.join().unwrap());
 
-    let mut node = mcs_lock::MCSNode::new();
-    let r = n.lock(&mut node);
+    let r = n.lock();
     println!("COUNT = {} (expected = {})", *r, NUM_LOOP * NUM_THREAD);
+
+    // 락을 잡고 있는 동안 try_lock은 실패한다.
+    assert!(n.try_lock().is_none());
+    drop(r);
+    let mut r = n.try_lock().expect("lock is free");
+    *r += 1;
+    println!("try_lock: COUNT = {}", *r);
 }
@@ -0,0 +1,132 @@
+use std::{
+    cell::UnsafeCell,
+    sync::atomic::{self, AtomicBool, AtomicUsize, Ordering},
+};
+
+use crate::RawLock;
+
+// Default로 만들 때의 슬롯 수
+const DEFAULT_NUM_SLOT: usize = 64;
+
+// 다른 슬롯과 같은 캐시 라인에 놓이지 않도록 정렬한다.
+#[repr(align(64))]
+struct Slot {
+    // 이 슬롯을 받은 스레드가 락을 획득할 수 있다면 true
+    granted: AtomicBool,
+}
+
+// Anderson의 배열 락
+// 티켓으로 슬롯을 정하고 자신의 슬롯만 보면서 스핀한다.
+// 해제할 때는 다음 슬롯에 권한을 넘긴다.
+pub struct RawAndersonLock {
+    slots: Box<[Slot]>,
+    mask: usize,
+    tail: AtomicUsize,
+    // 슬롯을 받은 스레드의 수
+    // 슬롯 수를 넘으면 같은 슬롯을 두 스레드가 사용하게 되므로 빈자리가 생길 때까지 기다린다.
+    active: AtomicUsize,
+    // 락을 가진 스레드의 슬롯. 락을 가진 스레드만 읽고 쓴다.
+    holder: UnsafeCell<usize>,
+}
+
+unsafe impl Send for RawAndersonLock {}
+unsafe impl Sync for RawAndersonLock {}
+
+impl RawAndersonLock {
+    // 슬롯 수는 동시에 락을 기다리는 스레드 수의 상한으로 2의 거듭제곱으로 올림한다.
+    pub fn new(num_slot: usize) -> Self {
+        assert!(num_slot > 0);
+        let num_slot = num_slot.next_power_of_two();
+        RawAndersonLock {
+            slots: (0..num_slot)
+                .map(|i| Slot {
+                    granted: AtomicBool::new(i == 0),
+                })
+                .collect(),
+            mask: num_slot - 1,
+            tail: AtomicUsize::new(0),
+            active: AtomicUsize::new(0),
+            holder: UnsafeCell::new(0),
+        }
+    }
+
+    pub fn num_slots(&self) -> usize {
+        self.slots.len()
+    }
+
+    // 슬롯이 빌 때까지 기다리지 않고 false를 반환한다.
+    fn enter(&self) -> bool {
+        let mut n = self.active.load(Ordering::Relaxed);
+        loop {
+            if n == self.slots.len() {
+                return false;
+            }
+            match self
+                .active
+                .compare_exchange_weak(n, n + 1, Ordering::Acquire, Ordering::Relaxed)
+            {
+                Ok(_) => return true,
+                Err(current) => n = current,
+            }
+        }
+    }
+
+    fn acquired(&self, slot: usize) {
+        atomic::fence(Ordering::Acquire);
+        // 한 바퀴 후에 같은 슬롯을 받는 스레드를 위해 되돌린다.
+        self.slots[slot].granted.store(false, Ordering::Relaxed);
+        unsafe { *self.holder.get() = slot };
+    }
+}
+
+impl Default for RawAndersonLock {
+    fn default() -> Self {
+        Self::new(DEFAULT_NUM_SLOT)
+    }
+}
+
+unsafe impl RawLock for RawAndersonLock {
+    fn lock(&self) {
+        while !self.enter() {
+            std::hint::spin_loop();
+        }
+
+        // 슬롯 수가 2의 거듭제곱이므로 tail이 한 바퀴 돌아도 슬롯의 순서가 유지된다.
+        let slot = self.tail.fetch_add(1, Ordering::Relaxed) & self.mask;
+        while !self.slots[slot].granted.load(Ordering::Relaxed) {
+            std::hint::spin_loop();
+        }
+        self.acquired(slot);
+    }
+
+    // 다음에 받을 슬롯에 이미 권한이 있을 때만 그 슬롯을 받는다.
+    // 슬롯을 받은 스레드 수가 슬롯 수보다 적으므로 그 권한은 이전 바퀴의 것이 아니다.
+    fn try_lock(&self) -> bool {
+        if !self.enter() {
+            return false;
+        }
+
+        let tail = self.tail.load(Ordering::Relaxed);
+        let slot = tail & self.mask;
+        if !self.slots[slot].granted.load(Ordering::Relaxed)
+            || self
+                .tail
+                .compare_exchange(tail, tail + 1, Ordering::Relaxed, Ordering::Relaxed)
+                .is_err()
+        {
+            self.active.fetch_sub(1, Ordering::Release);
+            return false;
+        }
+        self.acquired(slot);
+        true
+    }
+
+    unsafe fn unlock(&self) {
+        let slot = *self.holder.get();
+        self.slots[(slot + 1) & self.mask]
+            .granted
+            .store(true, Ordering::Release);
+        // 슬롯을 되돌린 것이 다음에 같은 슬롯을 받는 스레드에 보이도록 한다.
+        self.active.fetch_sub(1, Ordering::Release);
+    }
+}
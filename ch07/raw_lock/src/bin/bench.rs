@@ -0,0 +1,167 @@
+use std::{
+    sync::{Arc, Barrier},
+    thread,
+    time::{Duration, Instant},
+};
+
+use clap::{Arg, Command};
+use raw_lock::{
+    Lock, RawAndersonLock, RawCLHLock, RawFairLock, RawLock, RawMCSLock, RawSpinLock,
+    RawTicketLock, DEFAULT_SPIN,
+};
+
+struct CommandArgs {
+    locks: Vec<String>,
+    num_threads: Vec<usize>,
+    num_op: usize,
+    spin: u32,
+}
+
+fn get_command_args() -> CommandArgs {
+    let matches = Command::new("bench")
+        .arg(
+            Arg::new("lock")
+                .short('l')
+                .long("lock")
+                .value_name("LOCK")
+                .num_args(1)
+                .help(
+                    "Lock to measure \
+                     (spin, ticket, mcs, clh, anderson, cohort, fair, futex, futex_ticket or all)",
+                )
+                .default_value("all"),
+        )
+        .arg(
+            Arg::new("num_thread")
+                .short('n')
+                .long("num_thread")
+                .value_name("NUM_THREAD")
+                .num_args(1..)
+                .value_delimiter(',')
+                .help("Numbers of threads to run (comma separated)")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("1,2,4,8,16"),
+        )
+        .arg(
+            Arg::new("num_op")
+                .short('o')
+                .long("num_op")
+                .value_name("NUM_OP")
+                .num_args(1)
+                .help("Number of lock acquisitions per thread")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("100000"),
+        )
+        .arg(
+            Arg::new("spin")
+                .short('s')
+                .long("spin")
+                .value_name("SPIN")
+                .num_args(1)
+                .help("Number of spins before sleeping on futex")
+                .value_parser(clap::value_parser!(u32)),
+        )
+        .get_matches();
+
+    let lock = matches.get_one::<String>("lock").unwrap();
+    let locks = match lock.as_str() {
+        "all" => [
+            "spin",
+            "ticket",
+            "mcs",
+            "clh",
+            "anderson",
+            "cohort",
+            "fair",
+            "futex",
+            "futex_ticket",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect(),
+        s => vec![s.to_string()],
+    };
+
+    CommandArgs {
+        locks,
+        num_threads: matches.get_many("num_thread").unwrap().cloned().collect(),
+        num_op: matches.get_one("num_op").cloned().unwrap(),
+        spin: matches.get_one("spin").cloned().unwrap_or(DEFAULT_SPIN),
+    }
+}
+
+// 각 스레드가 락을 획득해서 카운터를 늘리는 데 걸린 시간
+fn bench<R: RawLock + 'static>(raw: R, num_thread: usize, num_op: usize) -> Duration {
+    let lock = Arc::new(Lock::with_raw(raw, 0));
+    let barrier = Arc::new(Barrier::new(num_thread));
+    let threads = (0..num_thread)
+        .map(|_| {
+            let lock = lock.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                barrier.wait();
+                let start = Instant::now();
+                for _ in 0..num_op {
+                    *lock.lock() += 1;
+                }
+                (start, Instant::now())
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // 각 스레드에서 잰 시각 중 가장 이른 시작부터 가장 늦은 종료까지를 측정한다.
+    let (start, end) = threads
+        .into_iter()
+        .map(|t| t.join().unwrap())
+        .reduce(|(s1, e1), (s2, e2)| (s1.min(s2), e1.max(e2)))
+        .unwrap();
+    assert_eq!(*lock.lock(), num_thread * num_op);
+    end - start
+}
+
+fn main() {
+    let args = get_command_args();
+    println!("ops = {} per thread, spin = {}", args.num_op, args.spin);
+    for &num_thread in args.num_threads.iter() {
+        println!("threads = {}", num_thread);
+        for name in args.locks.iter() {
+            let num_op = args.num_op;
+            let elapsed = match name.as_str() {
+                "spin" => bench(RawSpinLock::new(), num_thread, num_op),
+                "ticket" => bench(RawTicketLock::new(), num_thread, num_op),
+                "mcs" => bench(RawMCSLock::new(), num_thread, num_op),
+                "clh" => bench(RawCLHLock::new(), num_thread, num_op),
+                // 모든 스레드가 슬롯을 받을 수 있도록 한다.
+                "anderson" => bench(RawAndersonLock::new(num_thread), num_thread, num_op),
+                #[cfg(target_os = "linux")]
+                "cohort" => bench(raw_lock::RawCohortLock::new(), num_thread, num_op),
+                "fair" => bench(RawFairLock::new(num_thread), num_thread, num_op),
+                #[cfg(target_os = "linux")]
+                "futex" => bench(
+                    raw_lock::RawFutexLock::with_spin(args.spin),
+                    num_thread,
+                    num_op,
+                ),
+                #[cfg(target_os = "linux")]
+                "futex_ticket" => bench(
+                    raw_lock::RawFutexTicketLock::with_spin(args.spin),
+                    num_thread,
+                    num_op,
+                ),
+                #[allow(unreachable_patterns)]
+                "cohort" | "futex" | "futex_ticket" => {
+                    println!("{:>12}: not supported on this OS", name);
+                    continue;
+                }
+                s => panic!("unknown lock: {}", s),
+            };
+            let total = num_thread * num_op;
+            println!(
+                "{:>12}: {:>12.0} ops/s, elapsed = {:?}",
+                name,
+                total as f64 / elapsed.as_secs_f64(),
+                elapsed
+            );
+        }
+    }
+}
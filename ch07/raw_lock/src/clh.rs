@@ -0,0 +1,129 @@
+use std::{
+    cell::{RefCell, UnsafeCell},
+    ptr,
+    sync::atomic::{self, AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+};
+
+use crate::RawLock;
+
+// 다른 스레드의 노드와 같은 캐시 라인에 놓이지 않도록 정렬한다.
+#[repr(align(64))]
+struct CLHNode {
+    // 락을 획득했거나 기다리는 중이라면 true
+    locked: AtomicBool,
+}
+
+// CLH 락
+// 큐의 맨 마지막 노드와 교환해서 얻은 앞 노드의 locked를 보면서 스핀한다.
+// MCS 락과 달리 다음 노드를 가리키는 포인터가 없고
+// 해제는 자신의 노드에 쓰고 waiters를 줄이기만 한다.
+pub struct RawCLHLock {
+    // 큐의 맨 마지막. 처음에는 해제된 더미 노드
+    tail: AtomicPtr<CLHNode>,
+    // 큐에 들어가려는 스레드와 들어간 스레드의 수
+    // try_lock은 맨 마지막 노드를 읽지 않고 이 값이 0일 때만 큐에 들어간다.
+    waiters: AtomicUsize,
+    // 락을 가진 스레드의 (노드, 앞 노드). 락을 가진 스레드만 읽고 쓴다.
+    holder: UnsafeCell<(*mut CLHNode, *mut CLHNode)>,
+}
+
+unsafe impl Send for RawCLHLock {}
+unsafe impl Sync for RawCLHLock {}
+
+// 스레드별 노드 풀
+// 다른 스레드가 노드의 주소를 참조하므로 Box로 둔다.
+thread_local! {
+    #[allow(clippy::vec_box)]
+    static NODE_POOL: RefCell<Vec<Box<CLHNode>>> = const { RefCell::new(Vec::new()) };
+}
+
+fn alloc_node() -> *mut CLHNode {
+    let node = NODE_POOL
+        .try_with(|pool| pool.borrow_mut().pop())
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| {
+            Box::new(CLHNode {
+                locked: AtomicBool::new(false),
+            })
+        });
+    node.locked.store(true, Ordering::Relaxed);
+    Box::into_raw(node)
+}
+
+fn free_node(node: *mut CLHNode) {
+    let node = unsafe { Box::from_raw(node) };
+    // 스레드 종료 중이라면 그냥 해제한다.
+    let _ = NODE_POOL.try_with(|pool| pool.borrow_mut().push(node));
+}
+
+impl RawCLHLock {
+    pub fn new() -> Self {
+        RawCLHLock {
+            tail: AtomicPtr::new(Box::into_raw(Box::new(CLHNode {
+                locked: AtomicBool::new(false),
+            }))),
+            waiters: AtomicUsize::new(0),
+            holder: UnsafeCell::new((ptr::null_mut(), ptr::null_mut())),
+        }
+    }
+
+    fn wait(&self, node: *mut CLHNode, pred: *mut CLHNode) {
+        while unsafe { (*pred).locked.load(Ordering::Relaxed) } {
+            std::hint::spin_loop();
+        }
+        atomic::fence(Ordering::Acquire);
+        unsafe { *self.holder.get() = (node, pred) };
+    }
+}
+
+impl Default for RawCLHLock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for RawCLHLock {
+    fn drop(&mut self) {
+        // 마지막 노드는 아무도 가지지 않는다.
+        drop(unsafe { Box::from_raw(*self.tail.get_mut()) });
+    }
+}
+
+unsafe impl RawLock for RawCLHLock {
+    fn lock(&self) {
+        let node = alloc_node();
+        self.waiters.fetch_add(1, Ordering::Relaxed);
+
+        // 자신을 큐의 맨 마지막으로 하고 앞 노드가 해제될 때까지 기다린다.
+        let pred = self.tail.swap(node, Ordering::AcqRel);
+        self.wait(node, pred);
+    }
+
+    // 큐가 비어 있을 때만 큐에 들어간다.
+    // 맨 마지막 노드는 교환하기 전에는 다른 스레드가 해제할 수 있으므로 읽지 않는다.
+    // waiters를 확인한 직후에 lock을 호출한 스레드가 먼저 교환했다면
+    // 그 스레드가 해제할 때까지 기다린다.
+    fn try_lock(&self) -> bool {
+        if self
+            .waiters
+            .compare_exchange(0, 1, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return false;
+        }
+
+        let node = alloc_node();
+        let pred = self.tail.swap(node, Ordering::AcqRel);
+        self.wait(node, pred);
+        true
+    }
+
+    unsafe fn unlock(&self) {
+        let (node, pred) = *self.holder.get();
+        // 자신의 노드는 다음 스레드가 참조하므로 앞 노드를 재사용한다.
+        (*node).locked.store(false, Ordering::Release);
+        free_node(pred);
+        self.waiters.fetch_sub(1, Ordering::Release);
+    }
+}
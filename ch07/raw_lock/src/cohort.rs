@@ -0,0 +1,188 @@
+use std::{
+    cell::{Cell, UnsafeCell},
+    fs,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use crate::{Lock, LockGuard, RawLock, RawTicketLock};
+
+// 전역 락을 반납하지 않고 같은 소켓 안에서 넘겨줄 수 있는 최대 횟수
+// 다른 소켓의 스레드가 기아 상태가 되지 않도록 제한한다.
+const MAX_PASS: usize = 64;
+
+// 전역 락
+// 티켓 락은 획득한 스레드와 다른 스레드가 해제해도 되므로 넘겨받은 스레드가 해제할 수 있다.
+#[repr(align(64))]
+struct Global {
+    lock: RawTicketLock,
+}
+
+// 소켓별 로컬 락
+#[repr(align(64))]
+struct Cohort {
+    lock: RawTicketLock,
+    // 앞의 스레드가 전역 락을 반납하지 않고 넘겨주었다면 true
+    // 로컬 락을 가진 스레드만 읽고 쓴다.
+    global_passed: AtomicBool,
+    // 연속해서 넘겨준 횟수
+    pass_count: AtomicUsize,
+}
+
+// 코호트 락 (C-TKT-TKT)
+// 먼저 소켓별 로컬 락을 획득하고, 같은 소켓에 기다리는 스레드가 있다면
+// 전역 락을 반납하지 않고 그 스레드에게 넘겨준다.
+// 락과 데이터의 캐시 라인이 소켓 사이를 오가는 횟수를 줄일 수 있다.
+pub struct RawCohortLock {
+    global: Global,
+    cohorts: Box<[Cohort]>,
+    // 락을 가진 스레드의 노드. 락을 가진 스레드만 읽고 쓴다.
+    holder: UnsafeCell<usize>,
+}
+
+unsafe impl Send for RawCohortLock {}
+unsafe impl Sync for RawCohortLock {}
+
+// 스레드가 실행 중인 소켓 번호
+// 처음 호출했을 때 getcpu로 CPU를 얻고 /sys에서 소켓 번호를 읽어 기억해 둔다.
+// 이후에 다른 소켓으로 옮겨지면 효율은 떨어지지만 배타 제어는 유지된다.
+pub fn current_node() -> usize {
+    thread_local! {
+        static NODE: Cell<Option<usize>> = const { Cell::new(None) };
+    }
+
+    NODE.with(|node| {
+        if let Some(n) = node.get() {
+            return n;
+        }
+        let cpu = unsafe { libc::sched_getcpu() };
+        let n = if cpu < 0 {
+            0
+        } else {
+            package_id(cpu as usize).unwrap_or(0)
+        };
+        node.set(Some(n));
+        n
+    })
+}
+
+fn package_id(cpu: usize) -> Option<usize> {
+    let path = format!(
+        "/sys/devices/system/cpu/cpu{}/topology/physical_package_id",
+        cpu
+    );
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+// 시스템의 소켓 수
+pub fn num_nodes() -> usize {
+    let Ok(entries) = fs::read_dir("/sys/devices/system/cpu") else {
+        return 1;
+    };
+    entries
+        .filter_map(|e| {
+            let name = e.ok()?.file_name().into_string().ok()?;
+            package_id(name.strip_prefix("cpu")?.parse().ok()?)
+        })
+        .max()
+        .map_or(1, |n| n + 1)
+}
+
+impl RawCohortLock {
+    // 소켓 수는 /sys에서 얻는다.
+    pub fn new() -> Self {
+        Self::with_nodes(num_nodes())
+    }
+
+    pub fn with_nodes(num_node: usize) -> Self {
+        assert!(num_node > 0);
+        RawCohortLock {
+            global: Global {
+                lock: RawTicketLock::new(),
+            },
+            cohorts: (0..num_node)
+                .map(|_| Cohort {
+                    lock: RawTicketLock::new(),
+                    global_passed: AtomicBool::new(false),
+                    pass_count: AtomicUsize::new(0),
+                })
+                .collect(),
+            holder: UnsafeCell::new(0),
+        }
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.cohorts.len()
+    }
+
+    // node의 코호트에서 기다린다. node는 소켓 수로 나눈 나머지를 사용한다.
+    pub fn lock_on(&self, node: usize) {
+        let node = node % self.cohorts.len();
+        let cohort = &self.cohorts[node];
+        cohort.lock.lock();
+        // 넘겨받았다면 이미 전역 락을 가지고 있다.
+        if !cohort.global_passed.load(Ordering::Relaxed) {
+            self.global.lock.lock();
+        }
+        unsafe { *self.holder.get() = node };
+    }
+
+    pub fn try_lock_on(&self, node: usize) -> bool {
+        let node = node % self.cohorts.len();
+        let cohort = &self.cohorts[node];
+        if !cohort.lock.try_lock() {
+            return false;
+        }
+        if !cohort.global_passed.load(Ordering::Relaxed) && !self.global.lock.try_lock() {
+            // 로컬 락만 반납한다. 뒤에 온 스레드는 전역 락을 직접 획득한다.
+            unsafe { cohort.lock.unlock() };
+            return false;
+        }
+        unsafe { *self.holder.get() = node };
+        true
+    }
+}
+
+impl Default for RawCohortLock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl RawLock for RawCohortLock {
+    // 현재 스레드의 소켓의 코호트에서 기다린다.
+    fn lock(&self) {
+        self.lock_on(current_node())
+    }
+
+    fn try_lock(&self) -> bool {
+        self.try_lock_on(current_node())
+    }
+
+    unsafe fn unlock(&self) {
+        let cohort = &self.cohorts[*self.holder.get()];
+
+        let count = cohort.pass_count.load(Ordering::Relaxed);
+        if count < MAX_PASS && cohort.lock.has_waiters() {
+            // 같은 소켓의 다음 스레드에게 전역 락째로 넘겨준다.
+            cohort.pass_count.store(count + 1, Ordering::Relaxed);
+            cohort.global_passed.store(true, Ordering::Relaxed);
+        } else {
+            cohort.pass_count.store(0, Ordering::Relaxed);
+            cohort.global_passed.store(false, Ordering::Relaxed);
+            self.global.lock.unlock();
+        }
+        cohort.lock.unlock();
+    }
+}
+
+impl<T> Lock<RawCohortLock, T> {
+    // 지정한 노드의 코호트에서 기다린다.
+    pub fn lock_on(&self, node: usize) -> LockGuard<'_, RawCohortLock, T> {
+        self.raw.lock_on(node);
+        unsafe { self.guard() }
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.raw.num_nodes()
+    }
+}
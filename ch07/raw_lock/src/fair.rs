@@ -0,0 +1,313 @@
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{self, AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, Weak,
+    },
+    thread::{self, ThreadId},
+};
+
+use crate::{Lock, RawLock};
+
+// Default로 만들 때의 슬롯 수
+const DEFAULT_NUM_PARTICIPANT: usize = 64;
+
+// 락을 가진 스레드가 없을 때의 holder
+const NO_HOLDER: usize = usize::MAX;
+// 슬롯 없이 획득한 스레드가 락을 가지고 있을 때의 holder
+const SLOTLESS: usize = usize::MAX - 1;
+
+// 공평한 락
+// 각 스레드는 처음 락을 사용할 때 슬롯을 빌리고 스레드가 종료하거나 release_slot을 호출할 때 되돌린다.
+//
+// 슬롯 수는 공평성이 보장되는 스레드 수의 상한이다.
+// 모든 슬롯이 빌려져 있을 때 락을 사용하는 스레드는 슬롯 없이 CAS로 획득한다.
+// 이 스레드는 해제하는 스레드에게서 넘겨받지 못하므로 기아 상태가 될 수 있고 fairness에도 나타나지 않는다.
+// 슬롯 수보다 많은 스레드가 오래 살아 있는 경우에는 release_slot으로 슬롯을 되돌린다.
+pub struct RawFairLock {
+    waiting: Box<[AtomicBool]>,
+    registry: Arc<Registry>,
+    lock: AtomicBool,
+    turn: AtomicUsize,
+    // 획득된 횟수. 락을 가진 스레드만 쓴다.
+    count: AtomicUsize,
+    // 락을 가진 스레드의 슬롯. 슬롯 없이 획득했다면 SLOTLESS
+    // 그 스레드만 쓰고 해제하기 전에 NO_HOLDER로 되돌린다.
+    holder: AtomicUsize,
+}
+
+// 빌린 슬롯
+// 스레드별로 기억하므로 락이 해제된 후에도 Weak이 남을 수 있다.
+struct Registry {
+    used: Box<[AtomicBool]>,
+    stats: Box<[Stat]>,
+    // 슬롯을 되돌린 스레드의 통계
+    finished: Mutex<Vec<ThreadStat>>,
+}
+
+struct Lease {
+    registry: Weak<Registry>,
+    idx: usize,
+}
+
+impl Drop for Lease {
+    fn drop(&mut self) {
+        if let Some(registry) = self.registry.upgrade() {
+            let stat = &registry.stats[self.idx];
+            if let Some(s) = stat.take() {
+                registry.finished.lock().unwrap().push(s);
+            }
+            registry.used[self.idx].store(false, Ordering::Release);
+        }
+    }
+}
+
+thread_local! {
+    static LEASES: RefCell<Vec<Lease>> = const { RefCell::new(Vec::new()) };
+}
+
+// 슬롯별 통계
+// 횟수는 락을 가진 스레드만 쓰고, 슬롯을 빌릴 때와 되돌릴 때 owner를 바꾼다.
+#[derive(Default)]
+struct Stat {
+    owner: Mutex<Option<ThreadId>>,
+    acquired: AtomicUsize,
+    max_bypass: AtomicUsize,
+}
+
+impl Stat {
+    fn get(&self) -> Option<ThreadStat> {
+        let owner = self.owner.lock().unwrap();
+        Some(ThreadStat {
+            thread: (*owner)?,
+            acquired: self.acquired.load(Ordering::Relaxed),
+            max_bypass: self.max_bypass.load(Ordering::Relaxed),
+        })
+    }
+
+    // 소유자를 없애고 그때까지의 통계를 반환한다.
+    fn take(&self) -> Option<ThreadStat> {
+        let mut owner = self.owner.lock().unwrap();
+        Some(ThreadStat {
+            thread: owner.take()?,
+            acquired: self.acquired.swap(0, Ordering::Relaxed),
+            max_bypass: self.max_bypass.swap(0, Ordering::Relaxed),
+        })
+    }
+}
+
+// 공평성 보고
+#[derive(Debug, Clone, Copy)]
+pub struct ThreadStat {
+    pub thread: ThreadId,
+    // 락을 획득한 횟수
+    pub acquired: usize,
+    // 기다리는 동안 다른 스레드가 먼저 획득한 횟수의 최댓값
+    pub max_bypass: usize,
+}
+
+impl RawFairLock {
+    // num_participant는 동시에 슬롯을 빌릴 수 있는 스레드 수
+    pub fn new(num_participant: usize) -> Self {
+        assert!(num_participant > 0);
+        RawFairLock {
+            waiting: (0..num_participant)
+                .map(|_| AtomicBool::new(false))
+                .collect(),
+            registry: Arc::new(Registry {
+                used: (0..num_participant)
+                    .map(|_| AtomicBool::new(false))
+                    .collect(),
+                stats: (0..num_participant).map(|_| Stat::default()).collect(),
+                finished: Mutex::new(Vec::new()),
+            }),
+            lock: AtomicBool::new(false),
+            turn: AtomicUsize::new(0),
+            count: AtomicUsize::new(0),
+            holder: AtomicUsize::new(NO_HOLDER),
+        }
+    }
+
+    pub fn num_participants(&self) -> usize {
+        self.waiting.len()
+    }
+
+    // 현재 스레드가 빌린 슬롯을 반환한다.
+    // 아직 빌리지 않았다면 빈 슬롯을 찾고 없다면 None
+    fn lease(&self) -> Option<usize> {
+        LEASES.with(|leases| {
+            let mut leases = leases.borrow_mut();
+            let registry = Arc::as_ptr(&self.registry);
+            if let Some(lease) = leases.iter().find(|l| l.registry.as_ptr() == registry) {
+                return Some(lease.idx);
+            }
+
+            let idx = self.registry.used.iter().position(|used| {
+                !used.load(Ordering::Relaxed)
+                    && used
+                        .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                        .is_ok()
+            })?;
+            *self.registry.stats[idx].owner.lock().unwrap() = Some(thread::current().id());
+            // 해제된 락의 슬롯은 여기서 정리한다.
+            leases.retain(|l| l.registry.strong_count() > 0);
+            leases.push(Lease {
+                registry: Arc::downgrade(&self.registry),
+                idx,
+            });
+            Some(idx)
+        })
+    }
+
+    // 현재 스레드가 빌린 슬롯을 되돌린다. 빌리지 않았다면 아무것도 하지 않는다.
+    // 슬롯 수보다 많은 스레드가 오래 살아 있는 경우에 다른 스레드가 락을 사용할 수 있게 한다.
+    // 다음에 락을 사용할 때 다시 빌린다.
+    //
+    // 락을 가지고 있는 동안 호출하면 패닉한다.
+    pub fn release_slot(&self) {
+        let lease = LEASES.with(|leases| {
+            let mut leases = leases.borrow_mut();
+            let registry = Arc::as_ptr(&self.registry);
+            let pos = leases
+                .iter()
+                .position(|l| l.registry.as_ptr() == registry)?;
+            Some(leases.swap_remove(pos))
+        });
+        if let Some(lease) = lease {
+            // 다른 스레드가 쓰는 holder는 그 스레드의 슬롯이므로 같아지지 않는다.
+            assert_ne!(
+                self.holder.load(Ordering::Relaxed),
+                lease.idx,
+                "release_slot while holding the lock"
+            );
+            drop(lease);
+        }
+    }
+
+    fn acquired(&self, idx: usize, start: usize) {
+        let count = self.count.load(Ordering::Relaxed);
+        self.count.store(count + 1, Ordering::Relaxed);
+        if idx == SLOTLESS {
+            self.holder.store(SLOTLESS, Ordering::Relaxed);
+            return;
+        }
+
+        let stat = &self.registry.stats[idx];
+        stat.acquired
+            .store(stat.acquired.load(Ordering::Relaxed) + 1, Ordering::Relaxed);
+        // 기다리기 시작한 후에 다른 스레드가 획득한 횟수
+        let bypass = count - start;
+        if bypass > stat.max_bypass.load(Ordering::Relaxed) {
+            stat.max_bypass.store(bypass, Ordering::Relaxed);
+        }
+        self.holder.store(idx, Ordering::Relaxed);
+    }
+
+    // 스레드별 획득 횟수와 최대 추월 횟수
+    // 슬롯을 되돌린 스레드를 먼저, 슬롯을 빌리고 있는 스레드를 나중에 반환한다.
+    // 다른 스레드가 락을 사용하는 중이라면 조금 오래된 값일 수 있다.
+    // 슬롯 없이 획득한 횟수는 포함하지 않는다.
+    pub fn fairness(&self) -> Vec<ThreadStat> {
+        let mut result = self.registry.finished.lock().unwrap().clone();
+        result.extend(self.registry.stats.iter().filter_map(Stat::get));
+        result
+    }
+}
+
+impl Default for RawFairLock {
+    fn default() -> Self {
+        Self::new(DEFAULT_NUM_PARTICIPANT)
+    }
+}
+
+unsafe impl RawLock for RawFairLock {
+    // 슬롯을 빌리지 못했다면 락이 해제될 때까지 CAS를 반복한다.
+    fn lock(&self) {
+        let Some(idx) = self.lease() else {
+            while self
+                .lock
+                .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+            {
+                while self.lock.load(Ordering::Relaxed) {
+                    std::hint::spin_loop();
+                }
+            }
+            self.acquired(SLOTLESS, 0);
+            return;
+        };
+
+        let start = self.count.load(Ordering::Relaxed);
+        self.waiting[idx].store(true, Ordering::Relaxed);
+
+        loop {
+            // 다른 스레드가 false를 설정한 경우 락 획득
+            if !self.waiting[idx].load(Ordering::Relaxed) {
+                break;
+            }
+
+            if !self.lock.load(Ordering::Relaxed)
+                && self
+                    .lock
+                    .compare_exchange_weak(false, true, Ordering::Relaxed, Ordering::Relaxed)
+                    .is_ok()
+            {
+                break;
+            }
+            std::hint::spin_loop();
+        }
+        atomic::fence(Ordering::Acquire);
+        self.acquired(idx, start);
+    }
+
+    // 다른 스레드가 락을 가지고 있다면 false
+    fn try_lock(&self) -> bool {
+        let idx = self.lease().unwrap_or(SLOTLESS);
+        let start = self.count.load(Ordering::Relaxed);
+        // waiting을 설정하지 않으므로 다른 스레드가 넘겨주는 일은 없다.
+        if self
+            .lock
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return false;
+        }
+        self.acquired(idx, start);
+        true
+    }
+
+    unsafe fn unlock(&self) {
+        let n = self.waiting.len();
+        let idx = self.holder.swap(NO_HOLDER, Ordering::Relaxed);
+
+        // 슬롯이 없다면 turn과 같아지지 않으므로 다음 슬롯은 turn부터 찾는다.
+        if idx != SLOTLESS {
+            self.waiting[idx].store(false, Ordering::Relaxed);
+        }
+
+        let turn = self.turn.load(Ordering::Relaxed);
+        let next = if turn == idx { (turn + 1) % n } else { turn };
+
+        if self.waiting[next].load(Ordering::Relaxed) {
+            self.turn.store(next, Ordering::Release);
+            self.waiting[next].store(false, Ordering::Release);
+        } else {
+            self.turn.store((next + 1) % n, Ordering::Relaxed);
+            self.lock.store(false, Ordering::Release);
+        }
+    }
+}
+
+impl<T> Lock<RawFairLock, T> {
+    pub fn num_participants(&self) -> usize {
+        self.raw.num_participants()
+    }
+
+    pub fn release_slot(&self) {
+        self.raw.release_slot()
+    }
+
+    pub fn fairness(&self) -> Vec<ThreadStat> {
+        self.raw.fairness()
+    }
+}
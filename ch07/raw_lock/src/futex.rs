@@ -0,0 +1,52 @@
+// Linux의 futex 시스템 콜
+//
+// 같은 프로세스 안에서만 사용하므로 FUTEX_PRIVATE_FLAG를 붙인다.
+use std::{ptr, sync::atomic::AtomicU32};
+
+// atomic의 값이 expected인 동안 잔다.
+// 값이 다르거나 시그널로 깨어나면 바로 반환하므로 호출자가 조건을 다시 확인해야 한다.
+pub(crate) fn wait(atomic: &AtomicU32, expected: u32) {
+    wait_bitset(atomic, expected, u32::MAX);
+}
+
+// bitset과 겹치는 비트를 지정해서 깨울 때만 깨어난다.
+pub(crate) fn wait_bitset(atomic: &AtomicU32, expected: u32, bitset: u32) {
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            atomic.as_ptr(),
+            libc::FUTEX_WAIT_BITSET | libc::FUTEX_PRIVATE_FLAG,
+            expected,
+            ptr::null::<libc::timespec>(),
+            ptr::null::<u32>(),
+            bitset,
+        );
+    }
+}
+
+// 자고 있는 스레드를 하나 깨운다.
+pub(crate) fn wake_one(atomic: &AtomicU32) {
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            atomic.as_ptr(),
+            libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG,
+            1,
+        );
+    }
+}
+
+// bitset과 겹치는 비트로 자고 있는 스레드를 모두 깨운다.
+pub(crate) fn wake_bitset(atomic: &AtomicU32, bitset: u32) {
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            atomic.as_ptr(),
+            libc::FUTEX_WAKE_BITSET | libc::FUTEX_PRIVATE_FLAG,
+            i32::MAX,
+            ptr::null::<libc::timespec>(),
+            ptr::null::<u32>(),
+            bitset,
+        );
+    }
+}
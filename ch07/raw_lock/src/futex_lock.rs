@@ -0,0 +1,86 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::{futex, RawLock, DEFAULT_SPIN};
+
+const UNLOCKED: u32 = 0;
+const LOCKED: u32 = 1;
+// 락이 획득되어 있고 자고 있는 스레드가 있을 수 있다.
+const CONTENDED: u32 = 2;
+
+// 잠시 스핀하고 나서 futex로 자는 락
+// 자고 있는 스레드가 있을 수 있을 때만 해제할 때 깨우는 시스템 콜을 부른다.
+pub struct RawFutexLock {
+    state: AtomicU32,
+    // 자기 전에 스핀하는 횟수
+    spin: u32,
+}
+
+impl RawFutexLock {
+    pub fn new() -> Self {
+        Self::with_spin(DEFAULT_SPIN)
+    }
+
+    pub fn with_spin(spin: u32) -> Self {
+        RawFutexLock {
+            state: AtomicU32::new(UNLOCKED),
+            spin,
+        }
+    }
+
+    #[cold]
+    fn lock_contended(&self) {
+        // 다른 스레드가 곧 해제할 것을 기대하고 잠시 스핀한다.
+        for _ in 0..self.spin {
+            let state = self.state.load(Ordering::Relaxed);
+            if state == UNLOCKED
+                && self
+                    .state
+                    .compare_exchange_weak(UNLOCKED, LOCKED, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            {
+                return;
+            }
+            // 이미 자고 있는 스레드가 있다면 스핀해도 먼저 획득할 수 없을 가능성이 높다.
+            if state == CONTENDED {
+                break;
+            }
+            std::hint::spin_loop();
+        }
+
+        // 자는 스레드가 있음을 표시한다.
+        // 이 경로로 획득했다면 다른 스레드가 자고 있을 수 있으므로 CONTENDED 상태로 둔다.
+        while self.state.swap(CONTENDED, Ordering::Acquire) != UNLOCKED {
+            futex::wait(&self.state, CONTENDED);
+        }
+    }
+}
+
+impl Default for RawFutexLock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl RawLock for RawFutexLock {
+    fn lock(&self) {
+        if self
+            .state
+            .compare_exchange(UNLOCKED, LOCKED, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            self.lock_contended();
+        }
+    }
+
+    fn try_lock(&self) -> bool {
+        self.state
+            .compare_exchange(UNLOCKED, LOCKED, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    unsafe fn unlock(&self) {
+        if self.state.swap(UNLOCKED, Ordering::Release) == CONTENDED {
+            futex::wake_one(&self.state);
+        }
+    }
+}
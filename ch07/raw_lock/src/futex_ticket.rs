@@ -0,0 +1,107 @@
+use std::sync::atomic::{self, AtomicU32, Ordering};
+
+use crate::{futex, RawLock, DEFAULT_SPIN};
+
+// 티켓의 하위 5비트를 futex의 bitset으로 사용한다.
+// 자고 있는 스레드가 32개 이하라면 다음 티켓을 가진 스레드만 깨어난다.
+// 그보다 많으면 32개 뒤의 티켓을 가진 스레드도 깨어나지만 확인 후에 다시 잔다.
+fn bit(ticket: u32) -> u32 {
+    1 << (ticket % 32)
+}
+
+// 잠시 스핀하고 나서 futex로 자는 티켓 락
+// 해제할 때 다음 티켓을 가진 스레드만 깨운다.
+pub struct RawFutexTicketLock {
+    ticket: AtomicU32,
+    // futex로 기다리는 주소
+    turn: AtomicU32,
+    // 자고 있거나 자려고 하는 스레드의 수
+    // 0이라면 해제할 때 시스템 콜을 부르지 않는다.
+    sleepers: AtomicU32,
+    // 자기 전에 스핀하는 횟수
+    spin: u32,
+}
+
+impl RawFutexTicketLock {
+    pub fn new() -> Self {
+        Self::with_spin(DEFAULT_SPIN)
+    }
+
+    pub fn with_spin(spin: u32) -> Self {
+        RawFutexTicketLock {
+            ticket: AtomicU32::new(0),
+            turn: AtomicU32::new(0),
+            sleepers: AtomicU32::new(0),
+            spin,
+        }
+    }
+
+    #[cold]
+    fn park(&self, ticket: u32) {
+        loop {
+            // sleepers를 늘린 후에 turn을 읽는다.
+            // 해제하는 스레드는 turn을 늘린 후에 sleepers를 읽으므로
+            // 어느 한쪽은 반드시 상대의 쓰기를 본다.
+            self.sleepers.fetch_add(1, Ordering::SeqCst);
+            let turn = self.turn.load(Ordering::SeqCst);
+            if turn != ticket {
+                // 그 사이에 turn이 바뀌었다면 futex가 바로 반환한다.
+                futex::wait_bitset(&self.turn, turn, bit(ticket));
+            }
+            self.sleepers.fetch_sub(1, Ordering::Relaxed);
+
+            if self.turn.load(Ordering::Relaxed) == ticket {
+                return;
+            }
+        }
+    }
+}
+
+impl Default for RawFutexTicketLock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl RawLock for RawFutexTicketLock {
+    fn lock(&self) {
+        let ticket = self.ticket.fetch_add(1, Ordering::Relaxed);
+        let mut acquired = false;
+        for _ in 0..self.spin {
+            let turn = self.turn.load(Ordering::Relaxed);
+            if turn == ticket {
+                acquired = true;
+                break;
+            }
+            // 앞에 여러 스레드가 기다리고 있다면 스핀해도 획득할 수 없으므로 바로 잔다.
+            if ticket.wrapping_sub(turn) > 1 {
+                break;
+            }
+            std::hint::spin_loop();
+        }
+        if !acquired {
+            self.park(ticket);
+        }
+        atomic::fence(Ordering::Acquire);
+    }
+
+    // 기다리는 스레드가 없을 때만 다음 티켓을 받는다.
+    fn try_lock(&self) -> bool {
+        let turn = self.turn.load(Ordering::Acquire);
+        self.ticket
+            .compare_exchange(
+                turn,
+                turn.wrapping_add(1),
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+    }
+
+    unsafe fn unlock(&self) {
+        let next = self.turn.fetch_add(1, Ordering::SeqCst).wrapping_add(1);
+        if self.sleepers.load(Ordering::SeqCst) > 0 {
+            futex::wake_bitset(&self.turn, bit(next));
+        }
+    }
+}
@@ -0,0 +1,155 @@
+// 락 알고리즘과 보호하는 데이터를 분리한다.
+//
+// 각 알고리즘은 RawLock으로 획득과 해제만 구현하고,
+// UnsafeCell과 가드는 Lock<R, T>가 공통으로 제공한다.
+use std::{
+    cell::UnsafeCell,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+mod anderson;
+mod clh;
+#[cfg(target_os = "linux")]
+mod cohort;
+mod fair;
+#[cfg(target_os = "linux")]
+mod futex;
+#[cfg(target_os = "linux")]
+mod futex_lock;
+#[cfg(target_os = "linux")]
+mod futex_ticket;
+mod mcs;
+mod spin;
+mod ticket;
+
+pub use anderson::RawAndersonLock;
+pub use clh::RawCLHLock;
+#[cfg(target_os = "linux")]
+pub use cohort::{current_node, num_nodes, RawCohortLock};
+pub use fair::{RawFairLock, ThreadStat};
+#[cfg(target_os = "linux")]
+pub use futex_lock::RawFutexLock;
+#[cfg(target_os = "linux")]
+pub use futex_ticket::RawFutexTicketLock;
+pub use mcs::{MCSNode, RawMCSLock};
+pub use spin::RawSpinLock;
+pub use ticket::RawTicketLock;
+
+// futex로 자기 전에 스핀하는 기본 횟수
+// 락을 짧게 잡는 경우에는 시스템 콜 없이 획득할 수 있을 만큼으로 한다.
+pub const DEFAULT_SPIN: u32 = 100;
+
+/// 데이터를 가지지 않는 락
+///
+/// # Safety
+/// lock 또는 try_lock이 성공하고 나서 unlock할 때까지
+/// 다른 스레드가 락을 획득하지 않음을 보장해야 한다.
+/// unlock의 메모리 쓰기는 다음에 획득한 스레드에 보여야 한다.
+pub unsafe trait RawLock: Default + Send + Sync {
+    fn lock(&self);
+
+    fn try_lock(&self) -> bool;
+
+    /// # Safety
+    /// 현재 스레드가 락을 가지고 있어야 한다.
+    unsafe fn unlock(&self);
+}
+
+pub struct Lock<R, T> {
+    raw: R,
+    data: UnsafeCell<T>,
+}
+
+// 획득한 스레드에서 해제하도록 Send를 구현하지 않는다.
+pub struct LockGuard<'a, R: RawLock, T> {
+    lock: &'a Lock<R, T>,
+    _marker: PhantomData<*const ()>,
+}
+
+unsafe impl<R: RawLock, T: Send> Sync for Lock<R, T> {}
+unsafe impl<R: RawLock, T: Send> Send for Lock<R, T> {}
+unsafe impl<R: RawLock, T: Sync> Sync for LockGuard<'_, R, T> {}
+
+pub type SpinLock<T> = Lock<RawSpinLock, T>;
+pub type TicketLock<T> = Lock<RawTicketLock, T>;
+pub type MCSLock<T> = Lock<RawMCSLock, T>;
+pub type CLHLock<T> = Lock<RawCLHLock, T>;
+pub type AndersonLock<T> = Lock<RawAndersonLock, T>;
+#[cfg(target_os = "linux")]
+pub type CohortLock<T> = Lock<RawCohortLock, T>;
+pub type FairLock<T> = Lock<RawFairLock, T>;
+#[cfg(target_os = "linux")]
+pub type FutexLock<T> = Lock<RawFutexLock, T>;
+#[cfg(target_os = "linux")]
+pub type FutexTicketLock<T> = Lock<RawFutexTicketLock, T>;
+
+impl<R: RawLock, T> Lock<R, T> {
+    pub fn new(v: T) -> Self {
+        Self::with_raw(R::default(), v)
+    }
+
+    pub fn with_raw(raw: R, v: T) -> Self {
+        Lock {
+            raw,
+            data: UnsafeCell::new(v),
+        }
+    }
+
+    pub fn lock(&self) -> LockGuard<'_, R, T> {
+        self.raw.lock();
+        unsafe { self.guard() }
+    }
+
+    pub fn try_lock(&self) -> Option<LockGuard<'_, R, T>> {
+        if self.raw.try_lock() {
+            Some(unsafe { self.guard() })
+        } else {
+            None
+        }
+    }
+
+    // 알고리즘 고유의 획득 방법(MCS의 lock_with 등)에서도 사용한다.
+    // 현재 스레드가 raw를 획득한 후에 호출해야 한다.
+    unsafe fn guard(&self) -> LockGuard<'_, R, T> {
+        LockGuard {
+            lock: self,
+            _marker: PhantomData,
+        }
+    }
+
+    // 배타적으로 빌리고 있으므로 락을 획득할 필요가 없다.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<R: RawLock, T: Default> Default for Lock<R, T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<R: RawLock, T> Drop for LockGuard<'_, R, T> {
+    fn drop(&mut self) {
+        unsafe { self.lock.raw.unlock() };
+    }
+}
+
+impl<R: RawLock, T> Deref for LockGuard<'_, R, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<R: RawLock, T> DerefMut for LockGuard<'_, R, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.lock.data.get() }
+    }
+}
@@ -0,0 +1,74 @@
+use std::{sync::Arc, thread};
+
+use raw_lock::{
+    Lock, RawAndersonLock, RawCLHLock, RawFairLock, RawLock, RawMCSLock, RawSpinLock, RawTicketLock,
+};
+
+const NUM_LOOP: usize = 100_000;
+const NUM_THREAD: usize = 4;
+
+// 같은 코드로 모든 알고리즘을 확인한다.
+fn check<R: RawLock + 'static>(name: &str, raw: R) {
+    let lock = Arc::new(Lock::with_raw(raw, 0));
+    (0..NUM_THREAD)
+        .map(|i| {
+            let lock0 = lock.clone();
+            thread::spawn(move || {
+                (0..NUM_LOOP).for_each(|n| {
+                    // 일부는 try_lock으로 획득해 본다.
+                    let mut data = if (i + n).is_multiple_of(4) {
+                        loop {
+                            if let Some(data) = lock0.try_lock() {
+                                break data;
+                            }
+                            std::hint::spin_loop();
+                        }
+                    } else {
+                        lock0.lock()
+                    };
+                    *data += 1;
+                });
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .for_each(|t| t.join().unwrap());
+
+    let data = lock.lock();
+    println!(
+        "{:>15}: COUNT = {} (expected = {})",
+        name,
+        *data,
+        NUM_LOOP * NUM_THREAD
+    );
+    assert_eq!(*data, NUM_LOOP * NUM_THREAD);
+
+    // 락을 잡고 있는 동안 try_lock은 실패한다.
+    assert!(lock.try_lock().is_none());
+    drop(data);
+    assert!(lock.try_lock().is_some());
+}
+
+fn main() {
+    check("spin", RawSpinLock::new());
+    check("ticket", RawTicketLock::new());
+    check("mcs", RawMCSLock::new());
+    check("clh", RawCLHLock::new());
+    // 스레드 수보다 적게 해서 빈 슬롯을 기다리는 경우도 확인한다.
+    check("anderson", RawAndersonLock::new(NUM_THREAD / 2));
+    check("fair", RawFairLock::new(NUM_THREAD));
+
+    #[cfg(target_os = "linux")]
+    {
+        use raw_lock::{RawCohortLock, RawFutexLock, RawFutexTicketLock};
+
+        // 소켓이 하나인 환경에서도 넘겨주는 경로를 지나도록 2개의 노드로 한다.
+        check("cohort", RawCohortLock::with_nodes(2));
+
+        check("futex", RawFutexLock::new());
+        check("futex_ticket", RawFutexTicketLock::new());
+        // 스핀하지 않고 바로 자는 경로도 확인한다.
+        check("futex(0)", RawFutexLock::with_spin(0));
+        check("futex_ticket(0)", RawFutexTicketLock::with_spin(0));
+    }
+}
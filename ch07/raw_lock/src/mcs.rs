@@ -0,0 +1,169 @@
+use std::{
+    cell::{RefCell, UnsafeCell},
+    ptr,
+    sync::atomic::{self, AtomicBool, AtomicPtr, Ordering},
+};
+
+use crate::{Lock, LockGuard, RawLock};
+
+// 큐의 노드
+// 각 스레드는 자신의 노드의 locked만 보면서 스핀한다.
+pub struct MCSNode {
+    next: AtomicPtr<MCSNode>,
+    locked: AtomicBool,
+}
+
+impl MCSNode {
+    pub fn new() -> MCSNode {
+        MCSNode {
+            next: AtomicPtr::new(ptr::null_mut()),
+            locked: AtomicBool::new(false),
+        }
+    }
+}
+
+impl Default for MCSNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 노드는 스레드별 풀에서 얻고 락을 가진 스레드의 노드를 holder에 기억한다.
+pub struct RawMCSLock {
+    // 큐의 맨 마지막
+    last: AtomicPtr<MCSNode>,
+    // (노드, 풀에서 얻은 노드라면 true). 락을 가진 스레드만 읽고 쓴다.
+    holder: UnsafeCell<(*mut MCSNode, bool)>,
+}
+
+unsafe impl Send for RawMCSLock {}
+unsafe impl Sync for RawMCSLock {}
+
+// 다른 스레드가 노드의 주소를 참조하므로 Box로 둔다.
+thread_local! {
+    #[allow(clippy::vec_box)]
+    static NODE_POOL: RefCell<Vec<Box<MCSNode>>> = const { RefCell::new(Vec::new()) };
+}
+
+fn alloc_node() -> *mut MCSNode {
+    let node = NODE_POOL
+        .try_with(|pool| pool.borrow_mut().pop())
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| Box::new(MCSNode::new()));
+    Box::into_raw(node)
+}
+
+fn free_node(node: *mut MCSNode) {
+    let node = unsafe { Box::from_raw(node) };
+    // 스레드 종료 중이라면 그냥 해제한다.
+    let _ = NODE_POOL.try_with(|pool| pool.borrow_mut().push(node));
+}
+
+impl RawMCSLock {
+    pub fn new() -> Self {
+        RawMCSLock {
+            last: AtomicPtr::new(ptr::null_mut()),
+            holder: UnsafeCell::new((ptr::null_mut(), false)),
+        }
+    }
+
+    // 호출자가 준비한 노드를 사용한다.
+    // 노드는 unlock할 때까지 다른 용도로 사용하거나 해제해서는 안 된다.
+    unsafe fn acquire(&self, ptr: *mut MCSNode, pooled: bool) {
+        let node = &*ptr;
+        node.next.store(ptr::null_mut(), Ordering::Relaxed);
+        node.locked.store(false, Ordering::Relaxed);
+
+        // 자신을 큐의 맨 마지막으로 한다.
+        let prev = self.last.swap(ptr, Ordering::Acquire);
+
+        if !prev.is_null() {
+            node.locked.store(true, Ordering::Relaxed);
+
+            // locked를 설정한 것이 앞의 스레드의 해제보다 먼저 보이도록 Release로 연결한다.
+            let prev = &*prev;
+            prev.next.store(ptr, Ordering::Release);
+
+            while node.locked.load(Ordering::Relaxed) {
+                std::hint::spin_loop();
+            }
+        }
+
+        atomic::fence(Ordering::Acquire);
+        *self.holder.get() = (ptr, pooled);
+    }
+}
+
+impl Default for RawMCSLock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl RawLock for RawMCSLock {
+    fn lock(&self) {
+        unsafe { self.acquire(alloc_node(), true) };
+    }
+
+    // 큐가 비어 있을 때만 획득한다.
+    fn try_lock(&self) -> bool {
+        let ptr = alloc_node();
+        unsafe {
+            (*ptr).next.store(ptr::null_mut(), Ordering::Relaxed);
+            (*ptr).locked.store(false, Ordering::Relaxed);
+        }
+        if self
+            .last
+            .compare_exchange(ptr::null_mut(), ptr, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            free_node(ptr);
+            return false;
+        }
+        unsafe { *self.holder.get() = (ptr, true) };
+        true
+    }
+
+    unsafe fn unlock(&self) {
+        let (ptr, pooled) = *self.holder.get();
+        let node = &*ptr;
+
+        // 자신이 맨끝 노드일 때
+        if node.next.load(Ordering::Acquire).is_null()
+            && self
+                .last
+                .compare_exchange(ptr, ptr::null_mut(), Ordering::Release, Ordering::Relaxed)
+                .is_ok()
+        {
+            if pooled {
+                free_node(ptr);
+            }
+            return;
+        }
+
+        // 자신의 다음 스레드가 lock 함수를 실행중일 때
+        let mut next = node.next.load(Ordering::Acquire);
+        while next.is_null() {
+            std::hint::spin_loop();
+            next = node.next.load(Ordering::Acquire);
+        }
+
+        // 자신의 다음 스레드를 실행 가능하게 설정한다.
+        let next = &*next;
+        next.locked.store(false, Ordering::Release);
+        if pooled {
+            free_node(ptr);
+        }
+    }
+}
+
+impl<T> Lock<RawMCSLock, T> {
+    // 호출자가 준비한 노드를 사용한다. 가드가 있는 동안 노드를 빌린다.
+    pub fn lock_with<'a>(&'a self, node: &'a mut MCSNode) -> LockGuard<'a, RawMCSLock, T> {
+        unsafe {
+            self.raw.acquire(node, false);
+            self.guard()
+        }
+    }
+}
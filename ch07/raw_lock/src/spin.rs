@@ -0,0 +1,48 @@
+use std::{
+    hint,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::RawLock;
+
+// TTAS 스핀락
+#[derive(Default)]
+pub struct RawSpinLock {
+    lock: AtomicBool,
+}
+
+impl RawSpinLock {
+    pub fn new() -> Self {
+        RawSpinLock {
+            lock: AtomicBool::new(false),
+        }
+    }
+}
+
+unsafe impl RawLock for RawSpinLock {
+    fn lock(&self) {
+        loop {
+            while self.lock.load(Ordering::Relaxed) {
+                hint::spin_loop();
+            }
+
+            if self
+                .lock
+                .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+            {
+                break;
+            }
+        }
+    }
+
+    fn try_lock(&self) -> bool {
+        self.lock
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    unsafe fn unlock(&self) {
+        self.lock.store(false, Ordering::Release);
+    }
+}
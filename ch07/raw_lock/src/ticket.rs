@@ -0,0 +1,46 @@
+use std::sync::atomic::{self, AtomicUsize, Ordering};
+
+use crate::RawLock;
+
+#[derive(Default)]
+pub struct RawTicketLock {
+    ticket: AtomicUsize,
+    turn: AtomicUsize,
+}
+
+impl RawTicketLock {
+    pub fn new() -> Self {
+        RawTicketLock {
+            ticket: AtomicUsize::new(0),
+            turn: AtomicUsize::new(0),
+        }
+    }
+
+    // 락을 가진 스레드가 호출한다. 뒤에 기다리는 스레드가 있다면 true
+    pub(crate) fn has_waiters(&self) -> bool {
+        self.ticket.load(Ordering::Relaxed) != self.turn.load(Ordering::Relaxed) + 1
+    }
+}
+
+unsafe impl RawLock for RawTicketLock {
+    fn lock(&self) {
+        let ticket = self.ticket.fetch_add(1, Ordering::Relaxed);
+        while self.turn.load(Ordering::Relaxed) != ticket {
+            std::hint::spin_loop();
+        }
+        atomic::fence(Ordering::Acquire);
+    }
+
+    // 기다리는 스레드가 없을 때만 다음 티켓을 받는다.
+    // ticket이 turn과 같다면 그 사이에 turn은 바뀌지 않으므로 바로 획득한 것이 된다.
+    fn try_lock(&self) -> bool {
+        let turn = self.turn.load(Ordering::Acquire);
+        self.ticket
+            .compare_exchange(turn, turn + 1, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    unsafe fn unlock(&self) {
+        self.turn.fetch_add(1, Ordering::Release);
+    }
+}